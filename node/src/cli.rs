@@ -7,6 +7,11 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Price sources of the kitties offchain worker in order of preference, e.g.
+	/// `kraken,binance`. Sources are `binance`, `coinbase` and `kraken`.
+	#[clap(long, value_delimiter = ',')]
+	pub price_sources: Vec<String>,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -50,4 +55,8 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Proof-of-existence utilities.
+	#[clap(subcommand)]
+	Poe(crate::poe_cmd::PoeCmd),
 }
@@ -0,0 +1,256 @@
+//! `poe` subcommands for inspecting proof-of-existence claims offline.
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use node_template_runtime::{opaque::Block, pallet_poe, AccountId, BlockNumber, Runtime};
+use pallet_poe_verifier::{claim_from_storage_key, claim_storage_key, proofs_storage_prefix};
+use sc_cli::{
+	BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, Result, SharedParams,
+};
+use sc_client_api::StorageProvider;
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	crypto::Ss58Codec, hashing::blake2_256, hexdisplay::HexDisplay, storage::StorageKey, Decode,
+	Get,
+};
+use sp_runtime::generic::BlockId;
+
+use crate::{rpc::poe::POE_PALLET_NAME, service::FullClient};
+
+/// Proof-of-existence utilities.
+#[derive(Debug, clap::Subcommand)]
+pub enum PoeCmd {
+	/// Print the claim bytes `created_claim` expects for a file.
+	Hash(HashCmd),
+
+	/// Look up the owner of a claim in the local database.
+	Lookup(LookupCmd),
+
+	/// Dump every claim stored in the local database.
+	Export(ExportCmd),
+}
+
+/// The `poe hash` command.
+#[derive(Debug, clap::Parser)]
+pub struct HashCmd {
+	/// File to hash.
+	pub file: PathBuf,
+}
+
+/// The `poe lookup` command.
+#[derive(Debug, clap::Parser)]
+pub struct LookupCmd {
+	/// File to hash, or the hex encoded claim itself.
+	pub claim: String,
+
+	/// Block hash or number to read the claim at. Defaults to the best block.
+	#[clap(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// Output format of `poe export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ArgEnum)]
+pub enum ExportFormat {
+	/// A JSON array of claim records.
+	Json,
+	/// One `claim,owner,block` line per claim.
+	Csv,
+}
+
+/// The `poe export` command.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCmd {
+	/// Output format.
+	#[clap(long, arg_enum, default_value = "json")]
+	pub format: ExportFormat,
+
+	/// Block hash or number to export the claims at. Defaults to the best block.
+	#[clap(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// A decoded `Proofs` entry.
+#[derive(Debug, Serialize)]
+struct ClaimRecord {
+	claim: String,
+	owner: String,
+	block: BlockNumber,
+}
+
+impl ClaimRecord {
+	fn decode(claim: &[u8], value: &[u8]) -> Result<Self> {
+		let (owner, block) = <(AccountId, BlockNumber)>::decode(&mut &value[..])
+			.map_err(|e| format!("Invalid claim record: {}", e))?;
+		Ok(Self { claim: to_hex(claim), owner: owner.to_ss58check(), block })
+	}
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	format!("0x{}", HexDisplay::from(&bytes))
+}
+
+/// The claim bytes for the contents of `file`: its blake2-256 digest.
+fn hash_file(file: &Path) -> Result<Vec<u8>> {
+	let content = fs::read(file)?;
+	Ok(blake2_256(&content).to_vec())
+}
+
+/// The claim named by `claim`: a hex encoded claim, or a file to hash.
+fn parse_claim(claim: &str) -> Result<Vec<u8>> {
+	let claim = if claim.starts_with("0x") {
+		sp_core::bytes::from_hex(claim).map_err(|e| format!("Invalid hex claim: {}", e))?
+	} else {
+		hash_file(Path::new(claim))?
+	};
+
+	let max_length = <Runtime as pallet_poe::Config>::MaxClaimLength::get();
+	if claim.len() > max_length as usize {
+		return Err(format!("Claim is longer than {} bytes", max_length).into())
+	}
+	Ok(claim)
+}
+
+fn block_id(client: &FullClient, at: &Option<BlockNumberOrHash>) -> Result<BlockId<Block>> {
+	match at {
+		Some(at) => Ok(at.parse::<Block>()?),
+		None => Ok(BlockId::hash(client.info().best_hash)),
+	}
+}
+
+impl HashCmd {
+	/// Run the `poe hash` command.
+	pub fn run(&self) -> Result<()> {
+		println!("{}", to_hex(&hash_file(&self.file)?));
+		Ok(())
+	}
+}
+
+impl LookupCmd {
+	/// Run the `poe lookup` command.
+	pub fn run(&self, client: Arc<FullClient>) -> Result<()> {
+		let claim = parse_claim(&self.claim)?;
+		let at = block_id(&client, &self.at)?;
+		let key = StorageKey(claim_storage_key(POE_PALLET_NAME, &claim));
+		match client.storage(&at, &key)? {
+			Some(value) => {
+				let record = ClaimRecord::decode(&claim, &value.0)?;
+				println!(
+					"claim: {}\nowner: {}\nblock: {}",
+					record.claim, record.owner, record.block
+				);
+			},
+			None => println!("claim {} does not exist", to_hex(&claim)),
+		}
+		Ok(())
+	}
+}
+
+impl ExportCmd {
+	/// Run the `poe export` command.
+	pub fn run(&self, client: Arc<FullClient>) -> Result<()> {
+		let at = block_id(&client, &self.at)?;
+		let prefix = StorageKey(proofs_storage_prefix(POE_PALLET_NAME));
+
+		let mut records = Vec::new();
+		for (key, value) in client.storage_pairs(&at, &prefix)? {
+			let claim = claim_from_storage_key(&key.0).ok_or("Invalid claim storage key")?;
+			records.push(ClaimRecord::decode(&claim, &value.0)?);
+		}
+
+		match self.format {
+			ExportFormat::Json =>
+				println!("{}", serde_json::to_string_pretty(&records).map_err(|e| e.to_string())?),
+			ExportFormat::Csv => {
+				println!("claim,owner,block");
+				for record in records {
+					println!("{},{},{}", record.claim, record.owner, record.block);
+				}
+			},
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for LookupCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+impl CliConfiguration for ExportCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_claim_hashes_files() {
+		let file = std::env::temp_dir().join(format!("poe-claim-{}", std::process::id()));
+		fs::write(&file, b"hello world").unwrap();
+		let claim = parse_claim(file.to_str().unwrap());
+		fs::remove_file(&file).unwrap();
+
+		assert_eq!(claim.unwrap(), blake2_256(b"hello world").to_vec());
+		assert!(parse_claim("/does/not/exist").is_err());
+	}
+
+	#[test]
+	fn parse_claim_checks_length() {
+		let max_length = <Runtime as pallet_poe::Config>::MaxClaimLength::get() as usize;
+		let longest = to_hex(&vec![7u8; max_length]);
+		assert_eq!(parse_claim(&longest).unwrap(), vec![7u8; max_length]);
+
+		let too_long = to_hex(&vec![7u8; max_length + 1]);
+		assert!(parse_claim(&too_long).is_err());
+		assert!(parse_claim("0xzz").is_err());
+	}
+}
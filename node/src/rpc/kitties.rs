@@ -0,0 +1,222 @@
+//! RPC methods querying `pallet_kitties` through its runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_kitties::{KittyId, KittyInfo};
+use pallet_kitties_runtime_api::KittiesApi as KittiesRuntimeApi;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Page size of `kitties_kittiesOf` when none is given.
+const DEFAULT_PAGE_SIZE: u32 = 20;
+/// Largest page size of `kitties_kittiesOf`.
+const MAX_PAGE_SIZE: u32 = 100;
+/// Deepest family tree `kitties_ancestors` walks.
+const MAX_DEPTH: u32 = 32;
+
+/// JSON form of [`KittyInfo`], with the listing flattened.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KittyDetails<AccountId, Balance, BlockNumber> {
+	/// Id of the kitty.
+	pub kitty_id: KittyId,
+	/// The 16 DNA bytes.
+	pub dna: Bytes,
+	/// The 8 name bytes.
+	pub name: Bytes,
+	/// 0 for created kitties, one more than the older parent for bred ones.
+	pub generation: u32,
+	/// Block the kitty was born at.
+	pub born_at: BlockNumber,
+	/// The kitty can't breed before this block.
+	pub cooldown_until: BlockNumber,
+	/// Current owner.
+	pub owner: AccountId,
+	/// Listing price, if the kitty is for sale.
+	pub price: Option<Balance>,
+	/// Block the listing expires at, if it does.
+	pub expires_at: Option<BlockNumber>,
+}
+
+impl<AccountId, Balance, BlockNumber> From<KittyInfo<AccountId, Balance, BlockNumber>>
+	for KittyDetails<AccountId, Balance, BlockNumber>
+{
+	fn from(info: KittyInfo<AccountId, Balance, BlockNumber>) -> Self {
+		let (price, expires_at) = match info.listing {
+			Some(listing) => (Some(listing.price), listing.expires_at),
+			None => (None, None),
+		};
+		KittyDetails {
+			kitty_id: info.kitty_id,
+			dna: info.kitty.dna.to_vec().into(),
+			name: info.kitty.name.to_vec().into(),
+			generation: info.kitty.generation,
+			born_at: info.kitty.born_at,
+			cooldown_until: info.kitty.cooldown_until,
+			owner: info.owner,
+			price,
+			expires_at,
+		}
+	}
+}
+
+/// Kitties RPC methods. All of them read the best block unless `at` is given.
+#[rpc(client, server)]
+pub trait KittiesApi<BlockHash, AccountId, Balance, BlockNumber> {
+	/// A kitty with its owner and listing, `null` if it isn't born.
+	#[method(name = "kitties_kitty")]
+	fn kitty(
+		&self,
+		kitty_id: KittyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<KittyDetails<AccountId, Balance, BlockNumber>>>;
+
+	/// A page of the kitties of `owner`, 20 by default and at most 100.
+	#[method(name = "kitties_kittiesOf")]
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		start: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<KittyDetails<AccountId, Balance, BlockNumber>>>;
+
+	/// Ancestors of a kitty up to `depth` generations back, at most 32, parents first.
+	#[method(name = "kitties_ancestors")]
+	fn ancestors(
+		&self,
+		kitty_id: KittyId,
+		depth: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<KittyId>>;
+
+	/// All descendants of a kitty, in id order.
+	#[method(name = "kitties_descendants")]
+	fn descendants(&self, kitty_id: KittyId, at: Option<BlockHash>) -> RpcResult<Vec<KittyId>>;
+
+	/// Kitties currently for sale.
+	#[method(name = "kitties_forSale")]
+	fn for_sale(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<KittyDetails<AccountId, Balance, BlockNumber>>>;
+}
+
+/// Error codes returned by the kitties RPC methods.
+pub enum Error {
+	/// The runtime API call failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// Implementation of [`KittiesApiServer`] on top of a full client.
+pub struct Kitties<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Kitties<C, Block> {
+	/// Create a new instance of the kitties RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error(e: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query kitties.",
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+impl<C, Block> Kitties<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	fn block_id(&self, at: Option<Block::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+	KittiesApiServer<Block::Hash, AccountId, Balance, BlockNumber> for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: KittiesRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn kitty(
+		&self,
+		kitty_id: KittyId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<KittyDetails<AccountId, Balance, BlockNumber>>> {
+		let info = self.client.runtime_api().kitty(&self.block_id(at), kitty_id);
+		Ok(info.map_err(runtime_error)?.map(Into::into))
+	}
+
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		start: Option<u32>,
+		limit: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<KittyDetails<AccountId, Balance, BlockNumber>>> {
+		let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+		let kitties = self.client.runtime_api().kitties_of(
+			&self.block_id(at),
+			owner,
+			start.unwrap_or_default(),
+			limit,
+		);
+		Ok(kitties.map_err(runtime_error)?.into_iter().map(Into::into).collect())
+	}
+
+	fn ancestors(
+		&self,
+		kitty_id: KittyId,
+		depth: u32,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<KittyId>> {
+		self.client
+			.runtime_api()
+			.ancestors(&self.block_id(at), kitty_id, depth.min(MAX_DEPTH))
+			.map_err(runtime_error)
+	}
+
+	fn descendants(&self, kitty_id: KittyId, at: Option<Block::Hash>) -> RpcResult<Vec<KittyId>> {
+		self.client
+			.runtime_api()
+			.descendants(&self.block_id(at), kitty_id)
+			.map_err(runtime_error)
+	}
+
+	fn for_sale(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<KittyDetails<AccountId, Balance, BlockNumber>>> {
+		let kitties = self.client.runtime_api().kitties_for_sale(&self.block_id(at));
+		Ok(kitties.map_err(runtime_error)?.into_iter().map(Into::into).collect())
+	}
+}
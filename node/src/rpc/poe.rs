@@ -0,0 +1,192 @@
+//! RPC methods exporting verifiable evidence of `pallet_poe` claims.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_poe_verifier::{claim_storage_key, ClaimProof};
+use sc_client_api::{BlockBackend, ProofProvider};
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_finality_grandpa::GRANDPA_ENGINE_ID;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT, Zero},
+};
+
+/// Name of the PoE pallet in `construct_runtime!`, which prefixes its storage keys.
+pub const POE_PALLET_NAME: &[u8] = b"PoeModule";
+
+/// How many blocks back from the last finalized one `poe_claimProof` looks for a justification.
+///
+/// GRANDPA stores one every `justification_period` blocks (512 in the node service) and at
+/// every authority set change.
+const MAX_JUSTIFICATION_SEARCH: u32 = 1024;
+
+/// JSON form of [`ClaimProof`], with all binary fields hex encoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimProofBundle<Header> {
+	/// The raw claim bytes.
+	pub claim: Bytes,
+	/// Header of the block the proof was taken at.
+	pub header: Header,
+	/// SCALE-encoded GRANDPA justification of `header`, if the node has one.
+	pub justification: Option<Bytes>,
+	/// Trie nodes proving the claim's `Proofs` entry against the header's state root.
+	pub proof: Vec<Bytes>,
+}
+
+impl<Header> From<ClaimProofBundle<Header>> for ClaimProof<Header> {
+	fn from(bundle: ClaimProofBundle<Header>) -> Self {
+		ClaimProof {
+			claim: bundle.claim.0,
+			header: bundle.header,
+			justification: bundle.justification.map(|j| j.0),
+			proof: bundle.proof.into_iter().map(|node| node.0).collect(),
+		}
+	}
+}
+
+/// PoE RPC methods.
+#[rpc(client, server)]
+pub trait PoeApi<BlockHash, Header> {
+	/// Collect the header, justification and storage proof of `claim` at block `at`.
+	///
+	/// Only some finalized blocks have a stored GRANDPA justification. Defaults to the most
+	/// recent of them, and fails if there is none among the last `MAX_JUSTIFICATION_SEARCH`
+	/// finalized blocks. A block passed as `at` without a justification gives a bundle the
+	/// verifier rejects.
+	#[method(name = "poe_claimProof")]
+	fn claim_proof(
+		&self,
+		claim: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<ClaimProofBundle<Header>>;
+}
+
+/// Error codes returned by the PoE RPC methods.
+pub enum Error {
+	/// The requested block is unknown to the node.
+	UnknownBlock,
+	/// The client failed to build the proof.
+	ProofError,
+	/// No recent finalized block has a justification, `at` must be passed.
+	NoJustification,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::UnknownBlock => 1,
+			Error::ProofError => 2,
+			Error::NoJustification => 3,
+		}
+	}
+}
+
+/// Implementation of [`PoeApiServer`] on top of a full client.
+pub struct Poe<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Poe<C, Block> {
+	/// Create a new instance of the PoE RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn call_error(code: Error, message: &str, data: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(code.into(), message, Some(data.to_string()))).into()
+}
+
+impl<C, Block> Poe<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block> + BlockBackend<Block>,
+{
+	/// The most recent finalized block with a GRANDPA justification.
+	fn latest_justified(&self) -> RpcResult<Block::Hash> {
+		let mut hash = self.client.info().finalized_hash;
+		for _ in 0..MAX_JUSTIFICATION_SEARCH {
+			let at = BlockId::hash(hash);
+			let justified = self
+				.client
+				.justifications(&at)
+				.map_err(|e| call_error(Error::ProofError, "Unable to read justifications.", e))?
+				.map_or(false, |justifications| justifications.get(GRANDPA_ENGINE_ID).is_some());
+			if justified {
+				return Ok(hash)
+			}
+
+			let header = self
+				.client
+				.header(at)
+				.map_err(|e| call_error(Error::UnknownBlock, "Unable to read header.", e))?
+				.ok_or_else(|| {
+					call_error(Error::UnknownBlock, "Unknown block.", format!("{:?}", hash))
+				})?;
+			if header.number().is_zero() {
+				break
+			}
+			hash = *header.parent_hash();
+		}
+
+		Err(call_error(
+			Error::NoJustification,
+			"No recent finalized block has a GRANDPA justification, pass `at`.",
+			format!("searched {} blocks", MAX_JUSTIFICATION_SEARCH),
+		))
+	}
+}
+
+impl<C, Block> PoeApiServer<Block::Hash, Block::Header> for Poe<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block> + BlockBackend<Block> + ProofProvider<Block> + Send + Sync + 'static,
+{
+	fn claim_proof(
+		&self,
+		claim: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<ClaimProofBundle<Block::Header>> {
+		let hash = match at {
+			Some(hash) => hash,
+			None => self.latest_justified()?,
+		};
+		let at = BlockId::hash(hash);
+
+		let header = self
+			.client
+			.header(BlockId::hash(hash))
+			.map_err(|e| call_error(Error::UnknownBlock, "Unable to read header.", e))?
+			.ok_or_else(|| {
+				call_error(Error::UnknownBlock, "Unknown block.", format!("{:?}", hash))
+			})?;
+
+		let justification = self
+			.client
+			.justifications(&at)
+			.map_err(|e| call_error(Error::ProofError, "Unable to read justifications.", e))?
+			.and_then(|justifications| justifications.into_justification(GRANDPA_ENGINE_ID));
+
+		let key = claim_storage_key(POE_PALLET_NAME, &claim);
+		let proof = self
+			.client
+			.read_proof(&at, &mut std::iter::once(key.as_slice()))
+			.map_err(|e| call_error(Error::ProofError, "Unable to build storage proof.", e))?;
+
+		Ok(ClaimProofBundle {
+			claim,
+			header,
+			justification: justification.map(Into::into),
+			proof: proof.into_iter_nodes().map(Into::into).collect(),
+		})
+	}
+}
@@ -1,7 +1,7 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use node_template_runtime::{self, opaque::Block, RuntimeApi};
-use sc_client_api::BlockBackend;
+use node_template_runtime::{self, opaque::Block, pallet_kitties::price_source, RuntimeApi};
+use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
@@ -9,6 +9,7 @@ use sc_keystore::LocalKeystore;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
 use std::{sync::Arc, time::Duration};
 
 // Our native executor instance.
@@ -88,14 +89,18 @@ pub fn new_partial(
 		)?;
 	let client = Arc::new(client);
 
+	// the oracle authorities of the dev and local chain specs are Alice, Bob and Charlie, prices
+	// need a node started with each of `--alice`, `--bob` and `--charlie` to reach the quorum
 	if config.offchain_worker.enabled {
-		let keystore = keystore_container.sync_keystore();
-		sp_keystore::SyncCryptoStore::sr25519_generate_new(
-			&*keystore,
-			node_template_runtime::pallet_kitties::KEY_TYPE,
-			Some("//Alice"),
-		)
-			.expect("Creating key with account Alice should succeed.");
+		if let Some(seed) = &config.dev_key_seed {
+			let keystore = keystore_container.sync_keystore();
+			sp_keystore::SyncCryptoStore::sr25519_generate_new(
+				&*keystore,
+				node_template_runtime::pallet_kitties::KEY_TYPE,
+				Some(seed),
+			)
+			.expect("Creating the offchain worker key from the dev seed should succeed.");
+		}
 	}
 
 	let telemetry = telemetry.map(|(worker, telemetry)| {
@@ -164,7 +169,12 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// `price_sources` replaces the price sources of the kitties offchain worker unless empty.
+pub fn new_full(
+	mut config: Configuration,
+	price_sources: &[String],
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -176,6 +186,22 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		other: (block_import, grandpa_link, mut telemetry),
 	} = new_partial(&config)?;
 
+	// without the flag, the list set through `offchain_localStorageSet` is kept, and the offchain
+	// worker falls back to every source if there is none
+	if !price_sources.is_empty() {
+		if let Some(name) = price_sources.iter().find(|name| price_source::source(name).is_none()) {
+			return Err(ServiceError::Other(format!("Unknown price source: {}", name)))
+		}
+		// read by the offchain worker from its persistent local storage
+		if let Some(mut storage) = backend.offchain_storage() {
+			storage.set(
+				STORAGE_PREFIX,
+				price_source::SOURCES_KEY,
+				price_sources.join(",").as_bytes(),
+			);
+		}
+	}
+
 	if let Some(url) = &config.keystore_remote {
 		match remote_keystore(url) {
 			Ok(k) => keystore_container.set_remote_keystore(k),
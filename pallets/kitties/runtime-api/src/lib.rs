@@ -0,0 +1,46 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API definition for `pallet_kitties`.
+
+use codec::Codec;
+use pallet_kitties::{genetics::Trait, KittyId, KittyInfo};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Decoded genes of a kitty, `None` if it doesn't exist.
+		fn traits(kitty_id: KittyId) -> Option<Vec<Trait>>;
+
+		/// The account approved to transfer a kitty, if any.
+		fn approved(kitty_id: KittyId) -> Option<AccountId>;
+
+		/// Whether `operator` may transfer and approve every kitty of `owner`.
+		fn is_approved_for_all(owner: AccountId, operator: AccountId) -> bool;
+
+		/// Whether `who` may transfer a kitty, as its owner or an approved operator.
+		fn is_approved(who: AccountId, kitty_id: KittyId) -> bool;
+
+		/// A kitty with its owner and listing, `None` if it isn't born.
+		fn kitty(kitty_id: KittyId) -> Option<KittyInfo<AccountId, Balance, BlockNumber>>;
+
+		/// Up to `limit` kitties of `owner`, skipping the first `start`.
+		fn kitties_of(
+			owner: AccountId,
+			start: u32,
+			limit: u32,
+		) -> Vec<KittyInfo<AccountId, Balance, BlockNumber>>;
+
+		/// Ancestors of a kitty up to `depth` generations back, parents first.
+		fn ancestors(kitty_id: KittyId, depth: u32) -> Vec<KittyId>;
+
+		/// All descendants of a kitty, in id order.
+		fn descendants(kitty_id: KittyId) -> Vec<KittyId>;
+
+		/// Kitties currently for sale.
+		fn kitties_for_sale() -> Vec<KittyInfo<AccountId, Balance, BlockNumber>>;
+	}
+}
@@ -0,0 +1,440 @@
+use crate::*;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	inherent::Vec,
+	pallet_prelude::*,
+	traits::{Currency, EnsureOrigin, ReservableCurrency},
+};
+use frame_system::{offchain::AppCrypto, RawOrigin};
+use sp_runtime::{
+	traits::{AccountIdConversion, Bounded, IdentifyAccount, Saturating},
+	FixedPointNumber, FixedU128, RuntimeAppPublic,
+};
+
+const SEED: u32 = 0;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 4u32.into());
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	fund::<T>(&caller);
+	caller
+}
+
+fn funded_account<T: Config>(name: &'static str) -> T::AccountId {
+	let who = account(name, 0, SEED);
+	fund::<T>(&who);
+	who
+}
+
+/// Create a Kitty of `owner` and reveal it right away.
+fn born_kitty<T: Config>(owner: &T::AccountId) -> KittyId {
+	let kitty_id = NextKittyId::<T>::get();
+	assert!(
+		Pallet::<T>::create_kitty(RawOrigin::Signed(owner.clone()).into(), *b"kittykit").is_ok()
+	);
+	let reveal_at = PendingBirths::<T>::get(kitty_id).unwrap().reveal_at;
+	Pallet::<T>::reveal_births(reveal_at);
+	kitty_id
+}
+
+/// Fill the Kitties of `who` with placeholder ids, leaving room for `room` more. The Kitties
+/// added afterwards are the last ones searched.
+fn fill_owned<T: Config>(who: &T::AccountId, room: u32) {
+	let count = T::MaxKittiesOwned::get().saturating_sub(room);
+	let kitty_ids: Vec<KittyId> = (0..count).map(|i| KittyId::MAX - i).collect();
+	OwnedKitties::<T>::insert(who, BoundedVec::try_from(kitty_ids).unwrap());
+}
+
+/// Add an offer of `buyer` to the offers on `kitty_id`, reserving its funds.
+fn add_offer<T: Config>(kitty_id: KittyId, buyer: T::AccountId, expires_at: T::BlockNumber) {
+	let amount = price::<T>();
+	assert!(T::Currency::reserve(&buyer, amount).is_ok());
+	assert!(Offers::<T>::try_append(kitty_id, Offer { buyer, amount, expires_at }).is_ok());
+}
+
+/// Add `count` offers of funded accounts to the offers on `kitty_id`.
+fn add_offers<T: Config>(kitty_id: KittyId, count: u32, expires_at: T::BlockNumber) {
+	for i in 0..count {
+		let buyer: T::AccountId = account("offerer", i, SEED);
+		fund::<T>(&buyer);
+		add_offer::<T>(kitty_id, buyer, expires_at);
+	}
+}
+
+fn price<T: Config>() -> BalanceOf<T> {
+	KittyPrice::<T>::get().saturating_mul(10u32.into())
+}
+
+/// Fill the oracle authority set with `count` accounts, from index 1 on.
+fn oracle_authorities<T: Config>(count: u32) -> Vec<T::AccountId> {
+	let authorities: Vec<T::AccountId> =
+		(1..=count).map(|i| account("authority", i, SEED)).collect();
+	OracleAuthorities::<T>::put(BoundedVec::try_from(authorities.clone()).unwrap());
+	authorities
+}
+
+/// A distinct valid symbol for every `i` below 1000, e.g. `SYM007USDT`.
+fn symbol(i: u32) -> Symbol {
+	let digits = [b'0' + (i / 100 % 10) as u8, b'0' + (i / 10 % 10) as u8, b'0' + (i % 10) as u8];
+	Symbol::try_from([&b"SYM"[..], &digits, b"USDT"].concat()).unwrap()
+}
+
+fn watchlist<T: Config>(count: u32) -> Vec<Symbol> {
+	let symbols: Vec<Symbol> = (1..=count).map(symbol).collect();
+	Watchlist::<T>::put(BoundedVec::try_from(symbols.clone()).unwrap());
+	symbols
+}
+
+benchmarks! {
+	create_kitty {
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller.clone()), *b"kittykit")
+	verify {
+		let reveal_at = frame_system::Pallet::<T>::block_number().saturating_add(T::RevealDelay::get());
+		assert_last_event::<T>(Event::KittyRequested { owner: caller, kitty_id: 0, reveal_at }.into());
+	}
+
+	// both parents are sired from another owner, which pays two fees on top of the price
+	breed {
+		let caller = funded_caller::<T>();
+		let owner = funded_account::<T>("owner");
+		fill_owned::<T>(&caller, 1);
+		fill_owned::<T>(&owner, 2);
+		let kitty_1 = born_kitty::<T>(&owner);
+		let kitty_2 = born_kitty::<T>(&owner);
+		let fee = KittyPrice::<T>::get();
+		for kitty_id in [kitty_1, kitty_2] {
+			assert!(Pallet::<T>::offer_siring(RawOrigin::Signed(owner.clone()).into(), kitty_id, fee, caller.clone()).is_ok());
+		}
+	}: _(RawOrigin::Signed(caller.clone()), kitty_1, kitty_2, *b"kittykit")
+	verify {
+		assert_eq!(KittyOwner::<T>::get(2), Some(caller));
+		assert!(PendingBirths::<T>::contains_key(2));
+	}
+
+	transfer {
+		let caller = funded_caller::<T>();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		fill_owned::<T>(&caller, 1);
+		fill_owned::<T>(&recipient, 1);
+		let kitty_id = born_kitty::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), recipient.clone(), kitty_id)
+	verify {
+		assert_last_event::<T>(Event::KittyTransferred { owner: caller, recipient, kitty_id }.into());
+	}
+
+	sale {
+		let caller = funded_caller::<T>();
+		let kitty_id = born_kitty::<T>(&caller);
+		let price = price::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, price, None)
+	verify {
+		assert_last_event::<T>(Event::KittyOnSale { owner: caller, kitty_id, price, expires_at: None }.into());
+	}
+
+	buy {
+		let caller = funded_caller::<T>();
+		let owner = funded_account::<T>("owner");
+		fill_owned::<T>(&caller, 1);
+		fill_owned::<T>(&owner, 1);
+		let kitty_id = born_kitty::<T>(&owner);
+		let price = price::<T>();
+		assert!(Pallet::<T>::sale(RawOrigin::Signed(owner.clone()).into(), kitty_id, price, None).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, price)
+	verify {
+		assert_last_event::<T>(Event::BuyKitty { buyer: caller, owner, kitty_id, price }.into());
+	}
+
+	cancel_sale {
+		let caller = funded_caller::<T>();
+		let kitty_id = born_kitty::<T>(&caller);
+		assert!(Pallet::<T>::sale(RawOrigin::Signed(caller.clone()).into(), kitty_id, price::<T>(), None).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert_last_event::<T>(Event::KittySaleCancelled { owner: caller, kitty_id }.into());
+	}
+
+	update_price {
+		let caller = funded_caller::<T>();
+		let kitty_id = born_kitty::<T>(&caller);
+		assert!(Pallet::<T>::sale(RawOrigin::Signed(caller.clone()).into(), kitty_id, price::<T>(), None).is_ok());
+		let price = price::<T>().saturating_mul(2u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, price)
+	verify {
+		assert_last_event::<T>(Event::KittyPriceUpdated { owner: caller, kitty_id, price }.into());
+	}
+
+	offer_siring {
+		let caller = funded_caller::<T>();
+		let allowed: T::AccountId = account("allowed", 0, SEED);
+		let kitty_id = born_kitty::<T>(&caller);
+		let fee = KittyPrice::<T>::get();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, fee, allowed.clone())
+	verify {
+		assert_last_event::<T>(Event::SiringOffered { owner: caller, kitty_id, fee, allowed }.into());
+	}
+
+	revoke_siring {
+		let caller = funded_caller::<T>();
+		let allowed: T::AccountId = account("allowed", 0, SEED);
+		let kitty_id = born_kitty::<T>(&caller);
+		assert!(Pallet::<T>::offer_siring(RawOrigin::Signed(caller.clone()).into(), kitty_id, KittyPrice::<T>::get(), allowed).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert_last_event::<T>(Event::SiringRevoked { owner: caller, kitty_id }.into());
+	}
+
+	create_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = born_kitty::<T>(&caller);
+		let reserve_price = price::<T>();
+		let duration = T::MaxAuctionDuration::get();
+		assert!(Pallet::<T>::sale(RawOrigin::Signed(caller.clone()).into(), kitty_id, reserve_price, None).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, AuctionKind::English, reserve_price, duration)
+	verify {
+		assert!(Auctions::<T>::contains_key(kitty_id));
+		assert_eq!(KittyOnSale::<T>::get(kitty_id), None);
+	}
+
+	// outbidding an English auction reserves the new bid and refunds the previous one
+	bid {
+		let caller = funded_caller::<T>();
+		let seller = funded_account::<T>("seller");
+		let bidder = funded_account::<T>("bidder");
+		let kitty_id = born_kitty::<T>(&seller);
+		let reserve_price = price::<T>();
+		assert!(Pallet::<T>::create_auction(RawOrigin::Signed(seller).into(), kitty_id, AuctionKind::English, reserve_price, T::MaxAuctionDuration::get()).is_ok());
+		assert!(Pallet::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, reserve_price).is_ok());
+		let amount = reserve_price.saturating_mul(2u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, amount)
+	verify {
+		assert_last_event::<T>(Event::BidPlaced { bidder: caller, kitty_id, amount }.into());
+	}
+
+	cancel_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = born_kitty::<T>(&caller);
+		assert!(Pallet::<T>::create_auction(RawOrigin::Signed(caller.clone()).into(), kitty_id, AuctionKind::English, price::<T>(), T::MaxAuctionDuration::get()).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert_last_event::<T>(Event::AuctionCancelled { seller: caller, kitty_id }.into());
+	}
+
+	// the offer list is full of expired offers, which are all unreserved
+	make_offer {
+		let o in 0 .. T::MaxOffersPerKitty::get();
+		let caller = funded_caller::<T>();
+		let owner = funded_account::<T>("owner");
+		let kitty_id = born_kitty::<T>(&owner);
+		let now = frame_system::Pallet::<T>::block_number();
+		add_offers::<T>(kitty_id, o, now);
+		let amount = price::<T>();
+		let expires_at = now.saturating_add(T::MaxAuctionDuration::get());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, amount, expires_at)
+	verify {
+		assert_last_event::<T>(Event::OfferMade { buyer: caller, kitty_id, amount, expires_at }.into());
+	}
+
+	withdraw_offer {
+		let caller = funded_caller::<T>();
+		let owner = funded_account::<T>("owner");
+		let kitty_id = born_kitty::<T>(&owner);
+		let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(T::MaxAuctionDuration::get());
+		assert!(Pallet::<T>::make_offer(RawOrigin::Signed(caller.clone()).into(), kitty_id, price::<T>(), expires_at).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert_last_event::<T>(Event::OfferWithdrawn { buyer: caller, kitty_id }.into());
+	}
+
+	// the accepted offer is the last one searched, all the others are expired and unreserved
+	accept_offer {
+		let o in 0 .. T::MaxOffersPerKitty::get() - 1;
+		let caller = funded_caller::<T>();
+		let buyer = funded_account::<T>("buyer");
+		fill_owned::<T>(&caller, 1);
+		fill_owned::<T>(&buyer, 1);
+		let kitty_id = born_kitty::<T>(&caller);
+		let amount = price::<T>();
+		let now = frame_system::Pallet::<T>::block_number();
+		add_offers::<T>(kitty_id, o, now);
+		add_offer::<T>(kitty_id, buyer.clone(), now.saturating_add(T::MaxAuctionDuration::get()));
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, buyer.clone())
+	verify {
+		assert_last_event::<T>(Event::OfferAccepted { owner: caller, buyer, kitty_id, amount }.into());
+	}
+
+	// the released Kitty is listed and has offers to unreserve
+	release {
+		let o in 0 .. T::MaxOffersPerKitty::get();
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&caller, 1);
+		let kitty_id = born_kitty::<T>(&caller);
+		let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(T::MaxAuctionDuration::get());
+		assert!(Pallet::<T>::sale(RawOrigin::Signed(caller.clone()).into(), kitty_id, price::<T>(), None).is_ok());
+		add_offers::<T>(kitty_id, o, expires_at);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert!(!Kitties::<T>::contains_key(kitty_id));
+	}
+
+	withdraw_treasury {
+		let origin = T::AdminOrigin::successful_origin();
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let treasury: T::AccountId = T::PalletId::get().into_account_truncating();
+		fund::<T>(&treasury);
+		let amount = price::<T>();
+	}: _<T::RuntimeOrigin>(origin, amount, beneficiary.clone())
+	verify {
+		assert_last_event::<T>(Event::TreasuryWithdrawn { amount, beneficiary }.into());
+	}
+
+	set_kitty_price {
+		let origin = T::AdminOrigin::successful_origin();
+		let price = price::<T>();
+	}: _<T::RuntimeOrigin>(origin, price)
+	verify {
+		assert_last_event::<T>(Event::KittyPriceSet { price }.into());
+	}
+
+	approve {
+		let caller = funded_caller::<T>();
+		let operator: T::AccountId = account("operator", 0, SEED);
+		let kitty_id = born_kitty::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, Some(operator.clone()))
+	verify {
+		assert_last_event::<T>(Event::Approval { owner: caller, kitty_id, operator: Some(operator) }.into());
+	}
+
+	set_approval_for_all {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert_last_event::<T>(Event::ApprovalForAll { owner: caller, operator, approved: true }.into());
+	}
+
+	// the caller is approved for all Kitties of the owner, checked after the single approval
+	transfer_from {
+		let caller: T::AccountId = whitelisted_caller();
+		let owner = funded_account::<T>("owner");
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		fill_owned::<T>(&owner, 1);
+		fill_owned::<T>(&recipient, 1);
+		let kitty_id = born_kitty::<T>(&owner);
+		assert!(Pallet::<T>::set_approval_for_all(RawOrigin::Signed(owner.clone()).into(), caller.clone(), true).is_ok());
+	}: _(RawOrigin::Signed(caller), owner.clone(), recipient.clone(), kitty_id)
+	verify {
+		assert_last_event::<T>(Event::KittyTransferred { owner, recipient, kitty_id }.into());
+	}
+
+	add_oracle_authority {
+		let origin = T::AdminOrigin::successful_origin();
+		oracle_authorities::<T>(T::MaxOracleAuthorities::get() - 1);
+		let who: T::AccountId = account("authority", 0, SEED);
+	}: _<T::RuntimeOrigin>(origin, who.clone())
+	verify {
+		assert_last_event::<T>(Event::OracleAuthorityAdded { who }.into());
+	}
+
+	// the removed authority is the last one searched
+	remove_oracle_authority {
+		let origin = T::AdminOrigin::successful_origin();
+		let who = oracle_authorities::<T>(T::MaxOracleAuthorities::get()).pop().unwrap();
+	}: _<T::RuntimeOrigin>(origin, who.clone())
+	verify {
+		assert_last_event::<T>(Event::OracleAuthorityRemoved { who }.into());
+	}
+
+	watch_symbol {
+		let origin = T::AdminOrigin::successful_origin();
+		watchlist::<T>(T::MaxWatchedSymbols::get() - 1);
+		let symbol = symbol(0);
+	}: _<T::RuntimeOrigin>(origin, symbol.clone())
+	verify {
+		assert_last_event::<T>(Event::SymbolWatched { symbol }.into());
+	}
+
+	// the unwatched symbol is the last one searched
+	unwatch_symbol {
+		let origin = T::AdminOrigin::successful_origin();
+		let symbol = watchlist::<T>(T::MaxWatchedSymbols::get()).pop().unwrap();
+	}: _<T::RuntimeOrigin>(origin, symbol.clone())
+	verify {
+		assert_last_event::<T>(Event::SymbolUnwatched { symbol }.into());
+	}
+
+	// the reporter is the last authority searched and its submission closes the round
+	unsigned_extrinsic_with_signed_payload {
+		let app_public = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(None);
+		let public: T::Public = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(app_public.clone()).into();
+		let reporter = public.clone().into_account();
+		let max = T::MaxOracleAuthorities::get();
+		let mut authorities = oracle_authorities::<T>(max - 1);
+		authorities.push(reporter);
+		OracleAuthorities::<T>::put(BoundedVec::try_from(authorities.clone()).unwrap());
+
+		let symbol = watchlist::<T>(1).pop().unwrap();
+		let quorum = T::OracleQuorum::get().clamp(1, max);
+		let price = FixedU128::from_u32(100);
+		let submissions: Vec<(T::AccountId, FixedU128)> =
+			authorities.into_iter().take(quorum as usize - 1).map(|who| (who, price)).collect();
+		RoundSubmissions::<T>::insert(&symbol, BoundedVec::try_from(submissions).unwrap());
+		Rounds::<T>::insert(&symbol, Round { id: 0, started_at: frame_system::Pallet::<T>::block_number() });
+
+		let payload = Payload { symbol: symbol.clone(), price, public };
+		let signature: T::Signature = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericSignature::from(app_public.sign(&payload.encode()).unwrap()).into();
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert!(Prices::<T>::contains_key(&symbol));
+	}
+
+	// the births are bred, which mixes the genes of both parents
+	reveal_births {
+		let b in 0 .. T::MaxBirthsPerBlock::get();
+		let reveal_at = frame_system::Pallet::<T>::block_number().saturating_add(T::RevealDelay::get());
+		for i in 0 .. b {
+			let owner: T::AccountId = account("owner", i, SEED);
+			let kitty_id = Pallet::<T>::get_next_id().unwrap();
+			assert!(Pallet::<T>::request_birth(&owner, kitty_id, *b"kittykit", 1, Some(([0; 16], [1; 16]))).is_ok());
+		}
+	}: {
+		Pallet::<T>::reveal_births(reveal_at);
+	}
+	verify {
+		assert_eq!(Kitties::<T>::iter().count() as u32, b);
+	}
+
+	// every auction has a bid and hands its Kitty over
+	settle_auctions {
+		let a in 0 .. T::MaxAuctionsPerBlock::get();
+		let duration = T::MaxAuctionDuration::get();
+		let end = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		for i in 0 .. a {
+			let seller: T::AccountId = account("seller", i, SEED);
+			let bidder: T::AccountId = account("bidder", i, SEED);
+			fund::<T>(&seller);
+			fund::<T>(&bidder);
+			fill_owned::<T>(&seller, 1);
+			fill_owned::<T>(&bidder, 1);
+			let kitty_id = born_kitty::<T>(&seller);
+			assert!(Pallet::<T>::create_auction(RawOrigin::Signed(seller).into(), kitty_id, AuctionKind::English, price::<T>(), duration).is_ok());
+			assert!(Pallet::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, price::<T>()).is_ok());
+		}
+	}: {
+		Pallet::<T>::settle_auctions(end);
+	}
+	verify {
+		assert!(AuctionsEndingAt::<T>::get(end).is_empty());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,32 +1,117 @@
 use codec::{Decode, Encode};
 use core::fmt;
-use frame_support::inherent::Vec;
-use serde::{Deserialize, Deserializer};
+use serde::{de::Error as _, Deserialize, Deserializer};
 use scale_info::TypeInfo;
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+/// Exponents beyond this move every digit out of the range of `FixedU128`.
+const MAX_EXPONENT: i64 = 1_000;
 
 #[derive(Deserialize, Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
 pub(crate) struct CoinPriceInfo {
-	mins: u32,
-	#[serde(deserialize_with = "de_string_to_bytes")]
-	price: Vec<u8>,
+	pub(crate) mins: u32,
+	#[serde(deserialize_with = "de_string_to_price")]
+	pub(crate) price: FixedU128,
 }
 
-
 impl fmt::Debug for CoinPriceInfo {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		write!(
-			f,
-			"{{ mins: {}, price: {} }}",
-			&self.mins,
-			sp_std::str::from_utf8(&self.price).map_err(|_| fmt::Error)?
-		)
+		write!(f, "{{ mins: {}, price: ", &self.mins)?;
+		fmt_price(&self.price, f)?;
+		write!(f, " }}")
 	}
 }
 
-pub fn de_string_to_bytes<'de, D>(de: D) -> Result<Vec<u8>, D::Error>
+/// Write `price` as a decimal number without trailing zeros, e.g. `27000.5`.
+fn fmt_price(price: &FixedU128, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	let integer = price.into_inner() / FixedU128::DIV;
+	let mut fraction = price.into_inner() % FixedU128::DIV;
+	if fraction == 0 {
+		return write!(f, "{}", integer)
+	}
+	let mut width = 18;
+	while fraction % 10 == 0 {
+		fraction /= 10;
+		width -= 1;
+	}
+	write!(f, "{}.{:0width$}", integer, fraction, width = width)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum PriceError {
+	/// Not a decimal number, optionally in exponent notation.
+	Malformed,
+	/// The price is above the maximum of `FixedU128`.
+	Overflow,
+}
+
+pub fn de_string_to_price<'de, D>(de: D) -> Result<FixedU128, D::Error>
 	where
 		D: Deserializer<'de>,
 {
 	let s: &str = Deserialize::deserialize(de)?;
-	Ok(s.as_bytes().to_vec())
+	parse_price(s.as_bytes()).map_err(|err| match err {
+		PriceError::Malformed => D::Error::custom("malformed price"),
+		PriceError::Overflow => D::Error::custom("price overflows FixedU128"),
+	})
+}
+
+/// Parse a decimal price such as `27000.50` or `2.70005e4`.
+///
+/// Digits past the 18 decimals of `FixedU128` are truncated. Signs are only allowed in the
+/// exponent, prices can't be negative.
+pub(crate) fn parse_price(price: &[u8]) -> Result<FixedU128, PriceError> {
+	let (mantissa, exponent) = match price.iter().position(|b| *b == b'e' || *b == b'E') {
+		Some(index) => (&price[..index], Some(&price[index + 1..])),
+		None => (price, None),
+	};
+	let exponent = match exponent {
+		Some(exponent) => parse_exponent(exponent)?,
+		None => 0,
+	};
+
+	let (integer, fraction) = match mantissa.iter().position(|b| *b == b'.') {
+		Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+		None => (mantissa, &[][..]),
+	};
+	let is_digits = |digits: &[u8]| digits.iter().all(u8::is_ascii_digit);
+	if integer.is_empty() && fraction.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+		return Err(PriceError::Malformed)
+	}
+
+	// the power of ten of the first digit, in units of `FixedU128`
+	let mut power = integer.len() as i64 - 1 + exponent + 18;
+	let mut inner: u128 = 0;
+	for digit in integer.iter().chain(fraction) {
+		let digit = (digit - b'0') as u128;
+		if power < 0 {
+			break
+		}
+		if digit != 0 {
+			let unit = 10u128.checked_pow(power as u32).ok_or(PriceError::Overflow)?;
+			inner = digit
+				.checked_mul(unit)
+				.and_then(|value| inner.checked_add(value))
+				.ok_or(PriceError::Overflow)?;
+		}
+		power -= 1;
+	}
+	Ok(FixedU128::from_inner(inner))
+}
+
+/// Parse an optionally signed exponent, clamped to `MAX_EXPONENT`.
+fn parse_exponent(exponent: &[u8]) -> Result<i64, PriceError> {
+	let (negative, digits) = match exponent.split_first() {
+		Some((b'-', digits)) => (true, digits),
+		Some((b'+', digits)) => (false, digits),
+		_ => (false, exponent),
+	};
+	if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+		return Err(PriceError::Malformed)
+	}
+
+	let value = digits
+		.iter()
+		.fold(0i64, |value, digit| (value * 10 + (digit - b'0') as i64).min(MAX_EXPONENT));
+	Ok(if negative { -value } else { value })
 }
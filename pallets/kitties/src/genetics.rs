@@ -0,0 +1,111 @@
+//! Genetic model of kitty DNA.
+//!
+//! The 16 DNA bytes hold eight genes of two bytes each: the dominant allele, then the recessive
+//! one. An allele codes for variant `allele % variants` of its gene, and a kitty shows the
+//! variant of its dominant alleles.
+
+use codec::{Decode, Encode};
+use frame_support::{inherent::Vec, RuntimeDebug};
+use scale_info::TypeInfo;
+use sp_io::hashing::blake2_256;
+use sp_runtime::Permill;
+
+pub type Dna = [u8; 16];
+
+/// A named gene, stored at byte `2 * gene as usize` of the DNA.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Gene {
+	Body,
+	Pattern,
+	BodyColor,
+	PatternColor,
+	EyeShape,
+	EyeColor,
+	Mouth,
+	Ears,
+}
+
+impl Gene {
+	pub const ALL: [Gene; 8] = [
+		Gene::Body,
+		Gene::Pattern,
+		Gene::BodyColor,
+		Gene::PatternColor,
+		Gene::EyeShape,
+		Gene::EyeColor,
+		Gene::Mouth,
+		Gene::Ears,
+	];
+
+	/// Offset of the dominant allele in the DNA, the recessive one follows it.
+	pub fn offset(self) -> usize {
+		self as usize * 2
+	}
+
+	/// Number of variants of the gene.
+	pub fn variants(self) -> u8 {
+		match self {
+			Gene::Body | Gene::Mouth | Gene::Ears => 8,
+			Gene::Pattern | Gene::EyeShape => 12,
+			Gene::BodyColor | Gene::PatternColor | Gene::EyeColor => 32,
+		}
+	}
+
+	/// The variant an allele codes for.
+	pub fn variant(self, allele: u8) -> u8 {
+		allele % self.variants()
+	}
+}
+
+/// A decoded gene of a kitty.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Trait {
+	pub gene: Gene,
+	/// The variant the kitty shows.
+	pub dominant: u8,
+	/// The hidden variant, which offspring may still inherit.
+	pub recessive: u8,
+}
+
+/// Decode every gene of `dna`.
+pub fn decode(dna: &Dna) -> Vec<Trait> {
+	Gene::ALL
+		.iter()
+		.map(|&gene| Trait {
+			gene,
+			dominant: gene.variant(dna[gene.offset()]),
+			recessive: gene.variant(dna[gene.offset() + 1]),
+		})
+		.collect()
+}
+
+/// DNA of a kitty bred from `dna_1` and `dna_2`.
+///
+/// Each parent passes one of its two alleles of every gene, chosen by `seed`. The allele coding
+/// for the lower variant is dominant. Each gene then mutates with probability `mutation_rate`,
+/// replacing its recessive allele with a random one.
+pub fn inherit(dna_1: &Dna, dna_2: &Dna, seed: &[u8], mutation_rate: Permill) -> Dna {
+	let mut dna = [0u8; 16];
+	for (index, gene) in Gene::ALL.iter().enumerate() {
+		let random = blake2_256(&(seed, index as u8).encode());
+		let offset = gene.offset();
+
+		let allele_1 = dna_1[offset + (random[0] & 1) as usize];
+		let allele_2 = dna_2[offset + ((random[0] >> 1) & 1) as usize];
+		let (dominant, mut recessive) =
+			if (gene.variant(allele_1), allele_1) <= (gene.variant(allele_2), allele_2) {
+				(allele_1, allele_2)
+			} else {
+				(allele_2, allele_1)
+			};
+
+		let roll = u16::from_le_bytes([random[1], random[2]]) as u32;
+		if Permill::from_rational(roll, u16::MAX as u32 + 1) < mutation_rate {
+			recessive = random[3];
+		}
+
+		dna[offset] = dominant;
+		dna[offset + 1] = recessive;
+	}
+	dna
+}
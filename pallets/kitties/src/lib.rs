@@ -11,8 +11,15 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 mod coin_price;
+pub mod genetics;
 mod migrations;
+mod nonfungibles;
+pub mod price_source;
+pub mod weights;
 
 use sp_core::crypto::KeyTypeId;
 
@@ -45,14 +52,30 @@ pub mod crypto {
 	}
 }
 
+/// Latest oracle prices, for other pallets.
+pub trait PriceProvider<BlockNumber> {
+	type Price;
+
+	/// The latest price of `symbol` and the block it was stored in, unless it is stale.
+	fn latest_price(symbol: &[u8]) -> Option<(Self::Price, BlockNumber)>;
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-	use crate::{coin_price::CoinPriceInfo, migrations};
+	pub use crate::weights::WeightInfo;
+	use crate::{
+		genetics, migrations,
+		price_source::{self, PriceSource},
+		PriceProvider,
+	};
 	use core::marker::PhantomData;
 	use frame_support::{
 		inherent::Vec,
 		pallet_prelude::*,
-		traits::{Currency, ExistenceRequirement, Len, Randomness},
+		storage::with_storage_layer,
+		traits::{
+			BalanceStatus, Currency, ExistenceRequirement, Len, ReservableCurrency, UnixTime,
+		},
 		PalletId,
 	};
 	use frame_system::{
@@ -64,12 +87,20 @@ pub mod pallet {
 	};
 	use sp_core::offchain::Duration;
 	use sp_io::hashing::blake2_128;
-	use sp_runtime::{offchain::http, traits::AccountIdConversion};
+	use sp_runtime::{
+		offchain::http,
+		traits::{
+			AccountIdConversion, IdentifyAccount, One, Saturating, UniqueSaturatedInto, Zero,
+		},
+		FixedPointNumber, FixedU128, PerThing, Perbill, Percent, Permill,
+	};
+	use sp_std::collections::btree_set::BTreeSet;
 
 	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 	pub struct Payload<Public> {
-		coin_price: CoinPriceInfo,
-		public: Public,
+		pub(crate) symbol: Symbol,
+		pub(crate) price: FixedU128,
+		pub(crate) public: Public,
 	}
 
 	impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public> {
@@ -78,8 +109,7 @@ pub mod pallet {
 		}
 	}
 
-	const ON_CHAIN_KEY: &[u8] = b"kitties_prefix";
-	const STORAGE_VERSION_NUM: u16 = 2;
+	const STORAGE_VERSION_NUM: u16 = 3;
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(STORAGE_VERSION_NUM);
 
 	#[pallet::pallet]
@@ -88,16 +118,138 @@ pub mod pallet {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	pub type KittyId = u32;
-	type BalanceOf<T> =
+	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type KittyOf<T> = Kitty<<T as frame_system::Config>::BlockNumber>;
+	pub type PendingBirthOf<T> = PendingBirth<<T as frame_system::Config>::BlockNumber>;
+	pub type ListingOf<T> = Listing<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub type SiringOfferOf<T> = SiringOffer<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+	pub type OfferOf<T> = Offer<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type KittyInfoOf<T> = KittyInfo<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type AuctionOf<T> = Auction<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type PriceRecordOf<T> = PriceRecord<<T as frame_system::Config>::BlockNumber>;
+	pub type RoundOf<T> = Round<<T as frame_system::Config>::BlockNumber>;
+	/// A trading pair such as `BTCUSDT`.
+	pub type Symbol = BoundedVec<u8, ConstU32<16>>;
+	pub type RoundId = u32;
 
 	/// A Kitty, represented by its unique Kitty ID and data.
 	#[derive(
 		Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
 	)]
-	pub struct Kitty {
+	pub struct Kitty<BlockNumber> {
 		pub dna: [u8; 16],
 		pub name: [u8; 8],
+		/// 0 for created Kitties, one more than the older parent for bred ones.
+		pub generation: u32,
+		pub born_at: BlockNumber,
+		/// The Kitty can't breed before this block.
+		pub cooldown_until: BlockNumber,
+	}
+
+	/// A Kitty paid for but not born yet. Its DNA comes from the hash of block `reveal_at`.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct PendingBirth<BlockNumber> {
+		pub name: [u8; 8],
+		pub generation: u32,
+		/// DNA of the parents when breeding, taken when the Kitty was requested.
+		pub parents_dna: Option<([u8; 16], [u8; 16])>,
+		pub reveal_at: BlockNumber,
+	}
+
+	/// The median price of the last round of a symbol to reach a quorum.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct PriceRecord<BlockNumber> {
+		pub price: FixedU128,
+		pub round: RoundId,
+		/// The number of oracle authorities that submitted a price in the round.
+		pub reporters: u32,
+		pub block: BlockNumber,
+		/// Unix time of the block, in milliseconds.
+		pub timestamp: u64,
+	}
+
+	/// The open price round of a symbol.
+	#[derive(
+		Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct Round<BlockNumber> {
+		pub id: RoundId,
+		/// The block of the first submission. The round is dropped `RoundDuration` blocks later
+		/// if it has no quorum by then.
+		pub started_at: BlockNumber,
+	}
+
+	/// A born Kitty with its owner and listing, as served to frontends.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct KittyInfo<AccountId, Balance, BlockNumber> {
+		pub kitty_id: KittyId,
+		pub kitty: Kitty<BlockNumber>,
+		pub owner: AccountId,
+		/// The listing, unless the Kitty is not for sale or the listing expired.
+		pub listing: Option<Listing<Balance, BlockNumber>>,
+	}
+
+	/// A Kitty listed for sale at a price chosen by its owner.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Listing<Balance, BlockNumber> {
+		pub price: Balance,
+		/// The listing can no longer be bought from this block on.
+		pub expires_at: Option<BlockNumber>,
+	}
+
+	/// Funds reserved by a buyer for a Kitty that may not be for sale.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Offer<AccountId, Balance, BlockNumber> {
+		pub buyer: AccountId,
+		pub amount: Balance,
+		/// The offer can no longer be accepted from this block on.
+		pub expires_at: BlockNumber,
+	}
+
+	/// Single-use right to breed with a Kitty, sold by its owner.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SiringOffer<AccountId, Balance> {
+		/// The only account that can use the offer.
+		pub allowed: AccountId,
+		/// Paid to the Kitty owner when breeding.
+		pub fee: Balance,
+	}
+
+	/// How the price of an auction is discovered.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum AuctionKind<Balance> {
+		/// Ascending bids, each reserved until outbid. The best bid wins when the auction ends.
+		English,
+		/// The price decays linearly from `start_price` to the reserve price, the first bid at
+		/// the current price wins.
+		Dutch { start_price: Balance },
+	}
+
+	/// A Kitty under auction.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Auction<AccountId, Balance, BlockNumber> {
+		pub seller: AccountId,
+		pub kind: AuctionKind<Balance>,
+		/// The lowest price the Kitty is sold for.
+		pub reserve_price: Balance,
+		pub start: BlockNumber,
+		/// The auction is settled at the start of this block.
+		pub end: BlockNumber,
+		/// Best English bid so far, reserved from the bidder.
+		pub best_bid: Option<(AccountId, Balance)>,
 	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -105,13 +257,67 @@ pub mod pallet {
 	pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The Kitty price until `set_kitty_price` is called.
 		#[pallet::constant]
-		type KittyPrice: Get<BalanceOf<Self>>;
+		type DefaultKittyPrice: Get<BalanceOf<Self>>;
 		type PalletId: Get<PalletId>;
+		/// Origin allowed to spend the pallet account, set the Kitty price and manage the oracle
+		/// authorities.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The maximum number of auctions ending in the same block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+		/// The maximum duration of an auction.
+		#[pallet::constant]
+		type MaxAuctionDuration: Get<Self::BlockNumber>;
+		/// The maximum number of open offers on a Kitty.
+		#[pallet::constant]
+		type MaxOffersPerKitty: Get<u32>;
+		/// The maximum number of Kitties an account can own.
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+		/// How long a generation 0 Kitty rests after breeding. Each generation adds as much.
+		#[pallet::constant]
+		type BreedingCooldown: Get<Self::BlockNumber>;
+		/// Blocks between requesting a Kitty and the block whose hash decides its DNA.
+		///
+		/// Must be at least 1, the author of the requesting block could choose its hash otherwise.
+		#[pallet::constant]
+		type RevealDelay: Get<Self::BlockNumber>;
+		/// The maximum number of births revealed by the same block.
+		#[pallet::constant]
+		type MaxBirthsPerBlock: Get<u32>;
+		/// Share of the Kitty price refunded to the owner of a released Kitty.
+		#[pallet::constant]
+		type ReleaseRefund: Get<Percent>;
+		/// Chance of each gene to mutate when breeding.
+		#[pallet::constant]
+		type MutationRate: Get<Permill>;
 		/// The identifier type for an offchain worker.
 		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// Time source for the timestamp of submitted prices.
+		type UnixTime: UnixTime;
+		/// The maximum number of accounts allowed to submit prices.
+		#[pallet::constant]
+		type MaxOracleAuthorities: Get<u32>;
+		/// The number of submissions that closes a price round.
+		#[pallet::constant]
+		type OracleQuorum: Get<u32>;
+		/// The number of blocks a price round stays open for submissions.
+		#[pallet::constant]
+		type RoundDuration: Get<Self::BlockNumber>;
+		/// The number of blocks after which a price is stale.
+		#[pallet::constant]
+		type PriceStaleAfter: Get<Self::BlockNumber>;
+		/// Submissions further than this from the median are reported as outliers.
+		#[pallet::constant]
+		type MaxPriceDeviation: Get<Permill>;
+		/// The maximum number of symbols on the watchlist.
+		#[pallet::constant]
+		type MaxWatchedSymbols: Get<u32>;
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -120,7 +326,19 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitties)]
-	pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, Kitty>;
+	pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, KittyOf<T>>;
+
+	/// Price of creating or breeding a Kitty, paid into the pallet account.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_price)]
+	pub type KittyPrice<T: Config> =
+		StorageValue<_, BalanceOf<T>, ValueQuery, T::DefaultKittyPrice>;
+
+	/// Price paid into the pallet account for each Kitty, the base of its release refund.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_deposit)]
+	pub type KittyDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_parents)]
@@ -131,9 +349,154 @@ pub mod pallet {
 	#[pallet::getter(fn kitty_owner)]
 	pub type KittyOwner<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
 
+	/// Kitties owned by each account, the reverse of `KittyOwner`.
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitties)]
+	pub type OwnedKitties<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<KittyId, T::MaxKittiesOwned>,
+		ValueQuery,
+	>;
+
+	/// Kitties paid for and waiting for their reveal block. They have an owner but no data yet.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_birth)]
+	pub type PendingBirths<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, PendingBirthOf<T>>;
+
+	/// Births to reveal at the start of the block after each reveal block.
+	#[pallet::storage]
+	#[pallet::getter(fn births_revealed_at)]
+	pub type BirthsRevealedAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<KittyId, T::MaxBirthsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Account allowed to transfer a Kitty on behalf of its owner, until the Kitty changes hands.
+	#[pallet::storage]
+	#[pallet::getter(fn approved)]
+	pub type Approvals<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
+
+	/// Operators allowed to transfer and approve every Kitty of an owner.
+	#[pallet::storage]
+	pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_on_sale)]
-	pub type KittyOnSale<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, ()>;
+	pub type KittyOnSale<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, ListingOf<T>>;
+
+	/// Account allowed to breed with a Kitty it doesn't own, once, and the fee it pays.
+	#[pallet::storage]
+	#[pallet::getter(fn siring_offer)]
+	pub type SiringOffers<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, SiringOfferOf<T>>;
+
+	/// Open offers on each Kitty. Expired offers stay reserved until withdrawn or pruned.
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		KittyId,
+		BoundedVec<OfferOf<T>, T::MaxOffersPerKitty>,
+		ValueQuery,
+	>;
+
+	/// Kitties under auction. They can't be transferred, sold or bred until it ends.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, AuctionOf<T>>;
+
+	/// Auctions to settle at the start of each block.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_ending_at)]
+	pub type AuctionsEndingAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<KittyId, T::MaxAuctionsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The latest price of each symbol, agreed on by the oracle authorities.
+	#[pallet::storage]
+	#[pallet::getter(fn price)]
+	pub type Prices<T: Config> = StorageMap<_, Blake2_128Concat, Symbol, PriceRecordOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn round)]
+	pub type Rounds<T: Config> = StorageMap<_, Blake2_128Concat, Symbol, RoundOf<T>, ValueQuery>;
+
+	/// The prices submitted in the open round of each symbol.
+	#[pallet::storage]
+	#[pallet::getter(fn round_submissions)]
+	pub type RoundSubmissions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		Symbol,
+		BoundedVec<(T::AccountId, FixedU128), T::MaxOracleAuthorities>,
+		ValueQuery,
+	>;
+
+	/// Accounts whose offchain worker keys may submit prices.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_authorities)]
+	pub type OracleAuthorities<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxOracleAuthorities>, ValueQuery>;
+
+	/// The symbols priced by the offchain workers.
+	#[pallet::storage]
+	#[pallet::getter(fn watchlist)]
+	pub type Watchlist<T: Config> =
+		StorageValue<_, BoundedVec<Symbol, T::MaxWatchedSymbols>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub oracle_authorities: Vec<T::AccountId>,
+		pub watchlist: Vec<Vec<u8>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { oracle_authorities: Vec::new(), watchlist: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let authorities =
+				BoundedVec::<_, T::MaxOracleAuthorities>::try_from(self.oracle_authorities.clone())
+					.expect("genesis oracle authorities exceed MaxOracleAuthorities");
+			OracleAuthorities::<T>::put(authorities);
+
+			let watchlist: Vec<Symbol> = self
+				.watchlist
+				.iter()
+				.map(|symbol| {
+					Symbol::try_from(symbol.clone())
+						.ok()
+						.filter(|symbol| Pallet::<T>::is_valid_symbol(symbol))
+						.expect("genesis watchlist symbols are uppercase pairs of up to 16 bytes")
+				})
+				.collect();
+			let watchlist = BoundedVec::<_, T::MaxWatchedSymbols>::try_from(watchlist)
+				.expect("genesis watchlist exceeds MaxWatchedSymbols");
+			Watchlist::<T>::put(watchlist);
+		}
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -149,12 +512,29 @@ pub mod pallet {
 		KittyCreated {
 			owner: T::AccountId,
 			kitty_id: KittyId,
-			kitty: Kitty,
+			kitty: KittyOf<T>,
 		},
 		KittyBreed {
 			owner: T::AccountId,
 			kitty_id: KittyId,
-			kitty: Kitty,
+			kitty: KittyOf<T>,
+		},
+		/// A Kitty was paid for, it is born once the hash of block `reveal_at` is known.
+		KittyRequested {
+			owner: T::AccountId,
+			kitty_id: KittyId,
+			reveal_at: T::BlockNumber,
+		},
+		/// A Kitty was burnt by its owner, who got `refund` back.
+		KittyReleased {
+			owner: T::AccountId,
+			kitty_id: KittyId,
+			refund: BalanceOf<T>,
+		},
+		/// A Kitty was burned through `nonfungibles::Mutate`, without refund.
+		KittyBurned {
+			owner: T::AccountId,
+			kitty_id: KittyId,
 		},
 		KittyTransferred {
 			owner: T::AccountId,
@@ -164,15 +544,142 @@ pub mod pallet {
 		KittyOnSale {
 			owner: T::AccountId,
 			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			expires_at: Option<T::BlockNumber>,
+		},
+		KittySaleCancelled {
+			owner: T::AccountId,
+			kitty_id: KittyId,
+		},
+		KittyPriceUpdated {
+			owner: T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
 		},
 		BuyKitty {
 			buyer: T::AccountId,
 			owner: T::AccountId,
 			kitty_id: KittyId,
+			price: BalanceOf<T>,
+		},
+		SiringOffered {
+			owner: T::AccountId,
+			kitty_id: KittyId,
+			fee: BalanceOf<T>,
+			allowed: T::AccountId,
+		},
+		/// A siring offer was used to breed, and its fee paid to the owner.
+		SiringUsed {
+			owner: T::AccountId,
+			breeder: T::AccountId,
+			kitty_id: KittyId,
+			fee: BalanceOf<T>,
+		},
+		SiringRevoked {
+			owner: T::AccountId,
+			kitty_id: KittyId,
 		},
-		SetOffchainCoin {
+		AuctionCreated {
+			seller: T::AccountId,
+			kitty_id: KittyId,
+			kind: AuctionKind<BalanceOf<T>>,
+			reserve_price: BalanceOf<T>,
+			end: T::BlockNumber,
+		},
+		BidPlaced {
+			bidder: T::AccountId,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+		},
+		AuctionWon {
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+		},
+		/// An auction ended without a winning bid.
+		AuctionExpired {
+			seller: T::AccountId,
+			kitty_id: KittyId,
+		},
+		/// The best bid of an auction could not be paid, the Kitty stays with the seller.
+		AuctionFailed {
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			kitty_id: KittyId,
+		},
+		AuctionCancelled {
+			seller: T::AccountId,
+			kitty_id: KittyId,
+		},
+		OfferMade {
+			buyer: T::AccountId,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+			expires_at: T::BlockNumber,
+		},
+		OfferWithdrawn {
+			buyer: T::AccountId,
+			kitty_id: KittyId,
+		},
+		OfferAccepted {
+			owner: T::AccountId,
+			buyer: T::AccountId,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+		},
+		/// `operator` may transfer the Kitty, or no one but the owner if `None`.
+		Approval {
+			owner: T::AccountId,
+			kitty_id: KittyId,
+			operator: Option<T::AccountId>,
+		},
+		ApprovalForAll {
+			owner: T::AccountId,
+			operator: T::AccountId,
+			approved: bool,
+		},
+		/// The admin origin paid `amount` out of the pallet account.
+		TreasuryWithdrawn {
+			amount: BalanceOf<T>,
+			beneficiary: T::AccountId,
+		},
+		KittyPriceSet {
+			price: BalanceOf<T>,
+		},
+		/// `symbol` is priced by the offchain workers from now on.
+		SymbolWatched {
+			symbol: Symbol,
+		},
+		/// `symbol` is no longer priced, its open round was dropped.
+		SymbolUnwatched {
+			symbol: Symbol,
+		},
+		OracleAuthorityAdded {
+			who: T::AccountId,
+		},
+		OracleAuthorityRemoved {
 			who: T::AccountId,
-			coin: BoundedVec<u8, ConstU32<3>>,
+		},
+		PriceSubmitted {
+			symbol: Symbol,
+			round: RoundId,
+			reporter: T::AccountId,
+			price: FixedU128,
+		},
+		/// A round reached the quorum, its median is the new price of `symbol`.
+		///
+		/// `outliers` submitted prices further than `MaxPriceDeviation` from the median.
+		RoundFinalized {
+			symbol: Symbol,
+			round: RoundId,
+			price: FixedU128,
+			outliers: Vec<T::AccountId>,
+		},
+		/// A round ran out of time before reaching the quorum, its submissions were dropped.
+		RoundExpired {
+			symbol: Symbol,
+			round: RoundId,
 		},
 	}
 
@@ -180,6 +687,12 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// KettyId overflow.
 		KittiesCountOverflow,
+		/// The account owns `MaxKittiesOwned` Kitties already.
+		TooManyKitties,
+		/// A parent is still cooling down from breeding.
+		KittyCoolingDown,
+		/// The reveal block already has `MaxBirthsPerBlock` births.
+		TooManyBirths,
 		InvalidKittyId,
 		SameKittyId,
 		NotOwner,
@@ -187,12 +700,62 @@ pub mod pallet {
 		NoOwner,
 		AlreadyOwned,
 		NotOnSale,
+		/// The listing expiry is not in the future.
+		InvalidExpiry,
+		ListingExpired,
+		/// The listing price is higher than the buyer's `max_price`.
+		PriceTooHigh,
+		NoSiringOffer,
+		/// The Kitty is under auction.
+		KittyLocked,
+		NotInAuction,
+		InvalidAuctionDuration,
+		/// A Dutch auction must start above its reserve price.
+		InvalidStartPrice,
+		TooManyAuctions,
+		AuctionEnded,
+		BidTooLow,
+		/// An English auction can't be cancelled once it has a bid.
+		AuctionHasBids,
+		/// The reserved funds of the best bid no longer cover it.
+		BidNotFunded,
+		TooManyOffers,
+		/// The buyer already has an open offer on the Kitty.
+		OfferAlreadyExists,
+		NoOffer,
+		OfferExpired,
+		/// The reserved funds of the offer no longer cover its amount.
+		OfferNotFunded,
+		/// The caller is neither the owner nor an approved operator of the Kitty.
+		NotApproved,
+		ApproveToSelf,
+		/// The submitted price is zero.
+		InvalidPrice,
+		/// The payload was not signed by an oracle authority.
+		NotOracleAuthority,
+		AlreadyOracleAuthority,
+		TooManyOracleAuthorities,
+		/// The oracle authority already submitted a price in this round.
+		AlreadySubmitted,
+		/// Symbols are made of uppercase letters and digits.
+		InvalidSymbol,
+		AlreadyWatched,
+		/// The symbol is not on the watchlist.
+		NotWatched,
+		TooManySymbols,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			// the hash of the parent block is known from now on
+			Self::reveal_births(n.saturating_sub(One::one()))
+				.saturating_add(Self::settle_auctions(n))
+		}
+
 		fn on_runtime_upgrade() -> Weight {
-			let weight = migrations::v2::migrate::<T>();
+			let weight =
+				migrations::v2::migrate::<T>().saturating_add(migrations::v3::migrate::<T>());
 
 			// update storage version
 			let current_version = Pallet::<T>::on_chain_storage_version();
@@ -205,13 +768,24 @@ pub mod pallet {
 		}
 
 		fn offchain_worker(_block_number: T::BlockNumber) {
-			let _coin = Self::fetch_coin_info();
-
-			if let Ok(info) = Self::fetch_coin_price_info() {
-				log::info!("OCW ==> coin price info: {:?}", info);
+			let watchlist = Watchlist::<T>::get();
+			if watchlist.is_empty() {
+				log::info!("OCW ==> No symbol on the watchlist");
+				return
+			}
 
-				// Retrieve the signer to sign the payload
-				let signer = Signer::<T, T::AuthorityId>::any_account();
+			// Retrieve the signer to sign the payload
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			for symbol in watchlist {
+				let name = sp_std::str::from_utf8(&symbol).unwrap_or_default();
+				let price = match Self::fetch_price(&symbol) {
+					Some(price) => price,
+					None => {
+						log::info!("OCW ==> Error while fetching the price of {}!", name);
+						continue
+					},
+				};
+				log::info!("OCW ==> {} price: {:?}", name, price);
 
 				// `send_unsigned_transaction` is returning a type of `Option<(Account<T>,
 				// Result<(), ()>)>`. 	 The returned result means:
@@ -220,7 +794,7 @@ pub mod pallet {
 				// 	 - `Some((account, Err(())))`: error occurred when sending the transaction
 				if let Some((_, res)) = signer.send_unsigned_transaction(
 					// this line is to prepare and return payload
-					|acct| Payload { coin_price: info.clone(), public: acct.public.clone() },
+					|acct| Payload { symbol: symbol.clone(), price, public: acct.public.clone() },
 					|payload, signature| Call::unsigned_extrinsic_with_signed_payload {
 						payload,
 						signature,
@@ -239,9 +813,8 @@ pub mod pallet {
 				} else {
 					// The case of `None`: no account is available for sending
 					log::error!("OCW ==> No local account available");
+					return
 				}
-			} else {
-				log::info!("OCW ==> Error while fetch coin price info!");
 			}
 		}
 	}
@@ -249,17 +822,16 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create a new Kitty.
-		/// This function will create a new Kitty, save it to the Kitties map, and emit a
-		/// KittyCreated event.
+		/// This function will reserve a new Kitty ID for the caller and emit a KittyRequested
+		/// event. The Kitty is born with a KittyCreated event once its reveal block is known.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::create_kitty())]
 		pub fn create_kitty(origin: OriginFor<T>, name: [u8; 8]) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			let kitty_id = Self::get_next_id()?;
-			let kitty = Kitty { dna: Self::random_value(&who), name };
 
-			let kitty_price = T::KittyPrice::get();
+			let kitty_price = KittyPrice::<T>::get();
 			T::Currency::transfer(
 				&who,
 				&Self::get_pallet_account_id(),
@@ -267,16 +839,14 @@ pub mod pallet {
 				ExistenceRequirement::KeepAlive,
 			)?;
 
-			Kitties::<T>::insert(kitty_id, &kitty);
-			KittyOwner::<T>::insert(kitty_id, &who);
-
-			Self::deposit_event(Event::KittyCreated { owner: who, kitty_id, kitty });
+			KittyDeposits::<T>::insert(kitty_id, kitty_price);
+			Self::request_birth(&who, kitty_id, name, 0, None)?;
 			Ok(())
 		}
 
-		/// Breed a new Kitty.
+		/// Breed a new Kitty, born once its reveal block is known.
 		#[pallet::call_index(1)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::breed())]
 		pub fn breed(
 			origin: OriginFor<T>,
 			kitty_id_1: KittyId,
@@ -285,17 +855,25 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameKittyId);
-			ensure!(Kitties::<T>::contains_key(kitty_id_1), Error::<T>::InvalidKittyId);
-			ensure!(Kitties::<T>::contains_key(kitty_id_2), Error::<T>::InvalidKittyId);
+			// parents the breeder doesn't own use up their siring offer
+			let siring_1 = Self::ensure_can_sire(&who, kitty_id_1)?;
+			let siring_2 = Self::ensure_can_sire(&who, kitty_id_2)?;
 
 			let kitty_id = Self::get_next_id()?;
-			let kitty_1 = Kitties::<T>::get(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
-			let kitty_2 = Kitties::<T>::get(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+			let mut kitty_1 = Kitties::<T>::get(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+			let mut kitty_2 = Kitties::<T>::get(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				kitty_1.cooldown_until <= now && kitty_2.cooldown_until <= now,
+				Error::<T>::KittyCoolingDown
+			);
 
-			let kitty =
-				Kitty { dna: Self::random_value_from_two_kitty(&who, kitty_1, kitty_2), name };
+			let generation = kitty_1.generation.max(kitty_2.generation).saturating_add(1);
+			kitty_1.cooldown_until = now.saturating_add(Self::cooldown(kitty_1.generation));
+			kitty_2.cooldown_until = now.saturating_add(Self::cooldown(kitty_2.generation));
 
-			let kitty_price = T::KittyPrice::get();
+			let kitty_price = KittyPrice::<T>::get();
 			T::Currency::transfer(
 				&who,
 				&Self::get_pallet_account_id(),
@@ -303,54 +881,87 @@ pub mod pallet {
 				ExistenceRequirement::KeepAlive,
 			)?;
 
-			Kitties::<T>::insert(kitty_id, &kitty);
-			KittyOwner::<T>::insert(kitty_id, &who);
+			Kitties::<T>::insert(kitty_id_1, &kitty_1);
+			Kitties::<T>::insert(kitty_id_2, &kitty_2);
+			KittyDeposits::<T>::insert(kitty_id, kitty_price);
+			Self::request_birth(
+				&who,
+				kitty_id,
+				name,
+				generation,
+				Some((kitty_1.dna, kitty_2.dna)),
+			)?;
 			KittyParents::<T>::insert(kitty_id, (kitty_id_1, kitty_id_2));
-
-			Self::deposit_event(Event::KittyBreed { owner: who, kitty_id, kitty });
+			for (parent_id, siring) in [(kitty_id_1, siring_1), (kitty_id_2, siring_2)] {
+				if let Some((owner, fee)) = siring {
+					T::Currency::transfer(&who, &owner, fee, ExistenceRequirement::KeepAlive)?;
+					SiringOffers::<T>::remove(parent_id);
+					Self::deposit_event(Event::SiringUsed {
+						owner,
+						breeder: who.clone(),
+						kitty_id: parent_id,
+						fee,
+					});
+				}
+			}
 
 			Ok(())
 		}
 
 		/// Transfer a Kitty.
 		#[pallet::call_index(2)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::transfer())]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			recipient: T::AccountId,
 			kitty_id: KittyId,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(KittyOwner::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
-
-			let owner = KittyOwner::<T>::get(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
-			ensure!(owner == who, Error::<T>::NotOwner);
+			Self::ensure_owner(&who, kitty_id)?;
+			Self::ensure_unlocked(kitty_id)?;
 
-			KittyOwner::<T>::insert(kitty_id, &recipient);
+			Self::change_owner(kitty_id, &recipient)?;
 			Self::deposit_event(Event::KittyTransferred { owner: who, recipient, kitty_id });
 			Ok(())
 		}
 
-		/// sale a Kitty.
+		/// sale a Kitty at `price`, optionally until block `expires_at`.
 		#[pallet::call_index(3)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn sale(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::sale())]
+		pub fn sale(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Kitties::<T>::get(kitty_id)
-				.ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
-
-			ensure!(KittyOwner::<T>::get(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
-			ensure!(KittyOnSale::<T>::get(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			Self::ensure_owner(&who, kitty_id)?;
+			Self::ensure_unlocked(kitty_id)?;
+			// an expired listing can be replaced
+			ensure!(
+				KittyOnSale::<T>::get(kitty_id).map_or(true, |listing| Self::is_expired(&listing)),
+				Error::<T>::AlreadyOnSale
+			);
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
 
-			KittyOnSale::<T>::insert(kitty_id, ());
-			Self::deposit_event(Event::KittyOnSale { owner: who, kitty_id });
+			KittyOnSale::<T>::insert(kitty_id, Listing { price, expires_at });
+			Self::deposit_event(Event::KittyOnSale { owner: who, kitty_id, price, expires_at });
 			Ok(())
 		}
 
-		/// buy a Kitty.
+		/// buy a Kitty, paying at most `max_price`.
 		#[pallet::call_index(4)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn buy(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::buy())]
+		pub fn buy(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Kitties::<T>::get(kitty_id)
 				.ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
@@ -358,48 +969,532 @@ pub mod pallet {
 				.ok_or::<DispatchError>(Error::<T>::NoOwner.into())?;
 
 			ensure!(owner != who, Error::<T>::AlreadyOwned);
-			ensure!(KittyOnSale::<T>::get(kitty_id).is_some(), Error::<T>::NotOnSale);
+			let listing = KittyOnSale::<T>::get(kitty_id).ok_or(Error::<T>::NotOnSale)?;
+			ensure!(!Self::is_expired(&listing), Error::<T>::ListingExpired);
+			ensure!(listing.price <= max_price, Error::<T>::PriceTooHigh);
+
+			T::Currency::transfer(&who, &owner, listing.price, ExistenceRequirement::KeepAlive)?;
+
+			Self::change_owner(kitty_id, &who)?;
+			Self::deposit_event(Event::BuyKitty {
+				buyer: who,
+				owner,
+				kitty_id,
+				price: listing.price,
+			});
+			Ok(())
+		}
 
-			let kitty_price = T::KittyPrice::get();
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::unsigned_extrinsic_with_signed_payload())]
+		pub fn unsigned_extrinsic_with_signed_payload(
+			origin: OriginFor<T>,
+			payload: Payload<T::Public>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
 
-			T::Currency::transfer(&who, &owner, kitty_price, ExistenceRequirement::KeepAlive)?;
+			log::info!(
+				"OCW ==> in call unsigned_extrinsic_with_signed_payload: {:?}",
+				payload.price
+			);
+
+			let Payload { symbol, price, public } = payload;
+			let reporter = public.into_account();
+			ensure!(Self::is_oracle_authority(&reporter), Error::<T>::NotOracleAuthority);
+			ensure!(Self::is_watched(&symbol), Error::<T>::NotWatched);
+			ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
+			Self::submit_price(symbol, reporter, price)
+		}
+
+		/// Take a Kitty off sale.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::cancel_sale())]
+		pub fn cancel_sale(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, kitty_id)?;
+			ensure!(KittyOnSale::<T>::contains_key(kitty_id), Error::<T>::NotOnSale);
 
 			KittyOnSale::<T>::remove(kitty_id);
-			KittyOwner::<T>::insert(kitty_id, &who);
-			Self::deposit_event(Event::BuyKitty { buyer: who, owner, kitty_id });
+			Self::deposit_event(Event::KittySaleCancelled { owner: who, kitty_id });
 			Ok(())
 		}
 
-		#[pallet::call_index(5)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn set_offchain_coin(
+		/// Change the price of a Kitty on sale, keeping its expiry.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::update_price())]
+		pub fn update_price(
 			origin: OriginFor<T>,
-			coin: BoundedVec<u8, ConstU32<3>>,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, kitty_id)?;
 
-			log::info!("EXTRINSIC ==> set key: {:?}", ON_CHAIN_KEY);
-			log::info!("EXTRINSIC ==> set value: {:?}", sp_std::str::from_utf8(&coin).unwrap());
-			sp_io::offchain_index::set(&ON_CHAIN_KEY, &coin.encode());
+			KittyOnSale::<T>::try_mutate(kitty_id, |listing| -> DispatchResult {
+				let listing = listing.as_mut().ok_or(Error::<T>::NotOnSale)?;
+				ensure!(!Self::is_expired(listing), Error::<T>::ListingExpired);
+				listing.price = price;
+				Ok(())
+			})?;
 
-			Self::deposit_event(Event::SetOffchainCoin { who, coin });
+			Self::deposit_event(Event::KittyPriceUpdated { owner: who, kitty_id, price });
 			Ok(())
 		}
 
-		#[pallet::call_index(6)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn unsigned_extrinsic_with_signed_payload(
+		/// Allow `allowed_account` to breed once with a Kitty it doesn't own, for `fee`.
+		///
+		/// Replaces any previous offer on the Kitty.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::offer_siring())]
+		pub fn offer_siring(
 			origin: OriginFor<T>,
-			payload: Payload<T::Public>,
-			_signature: T::Signature,
+			kitty_id: KittyId,
+			fee: BalanceOf<T>,
+			allowed_account: T::AccountId,
 		) -> DispatchResult {
-			ensure_none(origin)?;
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, kitty_id)?;
+			ensure!(allowed_account != who, Error::<T>::AlreadyOwned);
 
-			log::info!(
-				"OCW ==> in call unsigned_extrinsic_with_signed_payload: {:?}",
-				payload.coin_price
+			SiringOffers::<T>::insert(
+				kitty_id,
+				SiringOffer { allowed: allowed_account.clone(), fee },
+			);
+			Self::deposit_event(Event::SiringOffered {
+				owner: who,
+				kitty_id,
+				fee,
+				allowed: allowed_account,
+			});
+			Ok(())
+		}
+
+		/// Withdraw an unused siring offer.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::revoke_siring())]
+		pub fn revoke_siring(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, kitty_id)?;
+			ensure!(SiringOffers::<T>::contains_key(kitty_id), Error::<T>::NoSiringOffer);
+
+			SiringOffers::<T>::remove(kitty_id);
+			Self::deposit_event(Event::SiringRevoked { owner: who, kitty_id });
+			Ok(())
+		}
+
+		/// Put a Kitty under auction for `duration` blocks. Any listing is cancelled.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			kind: AuctionKind<BalanceOf<T>>,
+			reserve_price: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, kitty_id)?;
+			Self::ensure_unlocked(kitty_id)?;
+			ensure!(
+				!duration.is_zero() && duration <= T::MaxAuctionDuration::get(),
+				Error::<T>::InvalidAuctionDuration
+			);
+			if let AuctionKind::Dutch { start_price } = kind {
+				ensure!(start_price > reserve_price, Error::<T>::InvalidStartPrice);
+			}
+
+			let start = frame_system::Pallet::<T>::block_number();
+			let end = start.saturating_add(duration);
+			AuctionsEndingAt::<T>::try_append(end, kitty_id)
+				.map_err(|_| Error::<T>::TooManyAuctions)?;
+			KittyOnSale::<T>::remove(kitty_id);
+			Auctions::<T>::insert(
+				kitty_id,
+				Auction { seller: who.clone(), kind, reserve_price, start, end, best_bid: None },
+			);
+
+			Self::deposit_event(Event::AuctionCreated {
+				seller: who,
+				kitty_id,
+				kind,
+				reserve_price,
+				end,
+			});
+			Ok(())
+		}
+
+		/// Bid on an auction.
+		///
+		/// English bids are reserved until outbid. A Dutch bid buys the Kitty at the current
+		/// price, `amount` being the most the bidder pays.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut auction = Auctions::<T>::get(kitty_id).ok_or(Error::<T>::NotInAuction)?;
+			ensure!(auction.seller != who, Error::<T>::AlreadyOwned);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < auction.end,
+				Error::<T>::AuctionEnded
+			);
+
+			match auction.kind {
+				AuctionKind::English => {
+					ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow);
+					if let Some((_, best)) = &auction.best_bid {
+						ensure!(amount > *best, Error::<T>::BidTooLow);
+					}
+
+					T::Currency::reserve(&who, amount)?;
+					if let Some((outbid, best)) = auction.best_bid.replace((who.clone(), amount)) {
+						T::Currency::unreserve(&outbid, best);
+					}
+					Auctions::<T>::insert(kitty_id, auction);
+					Self::deposit_event(Event::BidPlaced { bidder: who, kitty_id, amount });
+				},
+				AuctionKind::Dutch { .. } => {
+					let price = Self::dutch_price(&auction);
+					ensure!(amount >= price, Error::<T>::BidTooLow);
+
+					T::Currency::transfer(
+						&who,
+						&auction.seller,
+						price,
+						ExistenceRequirement::KeepAlive,
+					)?;
+					Auctions::<T>::remove(kitty_id);
+					Self::remove_ending(kitty_id, auction.end);
+					Self::change_owner(kitty_id, &who)?;
+					Self::deposit_event(Event::AuctionWon {
+						seller: auction.seller,
+						buyer: who,
+						kitty_id,
+						price,
+					});
+				},
+			}
+			Ok(())
+		}
+
+		/// Cancel an auction that has no bid yet.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		pub fn cancel_auction(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let auction = Auctions::<T>::get(kitty_id).ok_or(Error::<T>::NotInAuction)?;
+			ensure!(auction.seller == who, Error::<T>::NotOwner);
+			ensure!(auction.best_bid.is_none(), Error::<T>::AuctionHasBids);
+
+			Auctions::<T>::remove(kitty_id);
+			Self::remove_ending(kitty_id, auction.end);
+			Self::deposit_event(Event::AuctionCancelled { seller: who, kitty_id });
+			Ok(())
+		}
+
+		/// Offer `amount` for a Kitty until block `expires_at`, reserving the funds.
+		///
+		/// Expired offers on the Kitty are unreserved, the weight is that of a full offer list.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::make_offer(T::MaxOffersPerKitty::get()))]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			ensure!(KittyOwner::<T>::get(kitty_id) != Some(who.clone()), Error::<T>::AlreadyOwned);
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+
+			Offers::<T>::try_mutate(kitty_id, |offers| -> DispatchResult {
+				Self::prune_expired_offers(offers);
+				ensure!(
+					!offers.iter().any(|offer| offer.buyer == who),
+					Error::<T>::OfferAlreadyExists
+				);
+				offers
+					.try_push(Offer { buyer: who.clone(), amount, expires_at })
+					.map_err(|_| Error::<T>::TooManyOffers)?;
+				T::Currency::reserve(&who, amount)
+			})?;
+
+			Self::deposit_event(Event::OfferMade { buyer: who, kitty_id, amount, expires_at });
+			Ok(())
+		}
+
+		/// Withdraw an offer, expired or not, and unreserve its funds.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::withdraw_offer())]
+		pub fn withdraw_offer(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Offers::<T>::try_mutate_exists(kitty_id, |maybe_offers| -> DispatchResult {
+				let offers = maybe_offers.as_mut().ok_or(Error::<T>::NoOffer)?;
+				let index = offers
+					.iter()
+					.position(|offer| offer.buyer == who)
+					.ok_or(Error::<T>::NoOffer)?;
+				let offer = offers.remove(index);
+				T::Currency::unreserve(&offer.buyer, offer.amount);
+				if offers.is_empty() {
+					*maybe_offers = None;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OfferWithdrawn { buyer: who, kitty_id });
+			Ok(())
+		}
+
+		/// Sell a Kitty to `buyer` for the amount of their offer.
+		///
+		/// Expired offers on the Kitty are unreserved, the weight is that of a full offer list.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::accept_offer(T::MaxOffersPerKitty::get()))]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			buyer: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, kitty_id)?;
+			Self::ensure_unlocked(kitty_id)?;
+
+			let mut offers = Offers::<T>::get(kitty_id);
+			let index = offers
+				.iter()
+				.position(|offer| offer.buyer == buyer)
+				.ok_or(Error::<T>::NoOffer)?;
+			let offer = offers.remove(index);
+			ensure!(
+				offer.expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::OfferExpired
 			);
 
+			let remaining = T::Currency::repatriate_reserved(
+				&offer.buyer,
+				&who,
+				offer.amount,
+				BalanceStatus::Free,
+			)?;
+			ensure!(remaining.is_zero(), Error::<T>::OfferNotFunded);
+
+			Self::prune_expired_offers(&mut offers);
+			if offers.is_empty() {
+				Offers::<T>::remove(kitty_id);
+			} else {
+				Offers::<T>::insert(kitty_id, offers);
+			}
+			Self::change_owner(kitty_id, &buyer)?;
+
+			Self::deposit_event(Event::OfferAccepted {
+				owner: who,
+				buyer,
+				kitty_id,
+				amount: offer.amount,
+			});
+			Ok(())
+		}
+
+		/// Burn a Kitty, refunding `ReleaseRefund` of the price paid for it from the pallet
+		/// account.
+		///
+		/// Open offers on the Kitty are unreserved, the weight is that of a full offer list.
+		/// `KittyParents` is kept, so the lineage of its offspring stays intact.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::release(T::MaxOffersPerKitty::get()))]
+		pub fn release(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&who, kitty_id)?;
+			Self::ensure_unlocked(kitty_id)?;
+
+			// the price paid for this Kitty, not the current one
+			let refund = T::ReleaseRefund::get() * KittyDeposits::<T>::get(kitty_id);
+			T::Currency::transfer(
+				&Self::get_pallet_account_id(),
+				&who,
+				refund,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::remove_kitty(&who, kitty_id);
+			Self::deposit_event(Event::KittyReleased { owner: who, kitty_id, refund });
+			Ok(())
+		}
+
+		/// Pay `amount` out of the pallet account, which keeps at least the existential deposit.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::withdraw_treasury())]
+		pub fn withdraw_treasury(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			T::Currency::transfer(
+				&Self::get_pallet_account_id(),
+				&beneficiary,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::TreasuryWithdrawn { amount, beneficiary });
+			Ok(())
+		}
+
+		/// Set the price of creating or breeding a Kitty.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_kitty_price())]
+		pub fn set_kitty_price(origin: OriginFor<T>, new_price: BalanceOf<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			KittyPrice::<T>::put(new_price);
+			Self::deposit_event(Event::KittyPriceSet { price: new_price });
+			Ok(())
+		}
+
+		/// Allow `operator` to transfer a Kitty until it changes hands, `None` clears the approval.
+		///
+		/// Operators approved for all Kitties of the owner can approve too.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			operator: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			let owner = KittyOwner::<T>::get(kitty_id).ok_or(Error::<T>::NoOwner)?;
+			ensure!(who == owner || Self::is_approved_for_all(&owner, &who), Error::<T>::NotOwner);
+			ensure!(operator.as_ref() != Some(&owner), Error::<T>::ApproveToSelf);
+
+			match &operator {
+				Some(operator) => Approvals::<T>::insert(kitty_id, operator),
+				None => Approvals::<T>::remove(kitty_id),
+			}
+			Self::deposit_event(Event::Approval { owner, kitty_id, operator });
+			Ok(())
+		}
+
+		/// Allow or forbid `operator` to transfer and approve every Kitty of the caller.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != operator, Error::<T>::ApproveToSelf);
+
+			if approved {
+				OperatorApprovals::<T>::insert(&who, &operator, ());
+			} else {
+				OperatorApprovals::<T>::remove(&who, &operator);
+			}
+			Self::deposit_event(Event::ApprovalForAll { owner: who, operator, approved });
+			Ok(())
+		}
+
+		/// Transfer a Kitty of `from` as its owner or an approved operator.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			recipient: T::AccountId,
+			kitty_id: KittyId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_owner(&from, kitty_id)?;
+			ensure!(Self::is_approved(&who, kitty_id), Error::<T>::NotApproved);
+			Self::ensure_unlocked(kitty_id)?;
+
+			Self::change_owner(kitty_id, &recipient)?;
+			Self::deposit_event(Event::KittyTransferred { owner: from, recipient, kitty_id });
+			Ok(())
+		}
+
+		/// Allow the offchain worker key of `who` to submit prices.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::add_oracle_authority())]
+		pub fn add_oracle_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			OracleAuthorities::<T>::try_mutate(|authorities| {
+				ensure!(!authorities.contains(&who), Error::<T>::AlreadyOracleAuthority);
+				authorities
+					.try_push(who.clone())
+					.map_err(|_| Error::<T>::TooManyOracleAuthorities)?;
+				Ok::<_, DispatchError>(())
+			})?;
+			Self::deposit_event(Event::OracleAuthorityAdded { who });
+			Ok(())
+		}
+
+		/// Stop accepting prices from `who`. The prices it submitted are kept.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::remove_oracle_authority())]
+		pub fn remove_oracle_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			OracleAuthorities::<T>::try_mutate(|authorities| {
+				let index = authorities
+					.iter()
+					.position(|authority| authority == &who)
+					.ok_or(Error::<T>::NotOracleAuthority)?;
+				authorities.remove(index);
+				Ok::<_, DispatchError>(())
+			})?;
+			Self::deposit_event(Event::OracleAuthorityRemoved { who });
+			Ok(())
+		}
+
+		/// Add `symbol`, a trading pair such as `ETHUSDT`, to the pairs priced by the offchain
+		/// workers.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::watch_symbol())]
+		pub fn watch_symbol(origin: OriginFor<T>, symbol: Symbol) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_valid_symbol(&symbol), Error::<T>::InvalidSymbol);
+
+			Watchlist::<T>::try_mutate(|watchlist| {
+				ensure!(!watchlist.contains(&symbol), Error::<T>::AlreadyWatched);
+				watchlist.try_push(symbol.clone()).map_err(|_| Error::<T>::TooManySymbols)?;
+				Ok::<_, DispatchError>(())
+			})?;
+			Self::deposit_event(Event::SymbolWatched { symbol });
+			Ok(())
+		}
+
+		/// Stop pricing `symbol`. Its last price is kept until it goes stale.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::unwatch_symbol())]
+		pub fn unwatch_symbol(origin: OriginFor<T>, symbol: Symbol) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Watchlist::<T>::try_mutate(|watchlist| {
+				let index = watchlist
+					.iter()
+					.position(|watched| watched == &symbol)
+					.ok_or(Error::<T>::NotWatched)?;
+				watchlist.remove(index);
+				Ok::<_, DispatchError>(())
+			})?;
+			Rounds::<T>::remove(&symbol);
+			RoundSubmissions::<T>::remove(&symbol);
+			Self::deposit_event(Event::SymbolUnwatched { symbol });
 			Ok(())
 		}
 	}
@@ -408,7 +1503,7 @@ pub mod pallet {
 		/// Get the next available Kitty ID.
 		/// This function will get the next available Kitty ID, and increment the NextKittyId
 		/// counter.
-		fn get_next_id() -> Result<KittyId, DispatchError> {
+		pub(crate) fn get_next_id() -> Result<KittyId, DispatchError> {
 			NextKittyId::<T>::try_mutate(|id| -> Result<KittyId, DispatchError> {
 				let current_id = *id;
 				*id = id
@@ -418,39 +1513,460 @@ pub mod pallet {
 			})
 		}
 
-		/// Get random value.
-		pub fn random_value(sender: &T::AccountId) -> [u8; 16] {
-			let payload = (
-				T::Randomness::random_seed(),
-				&sender,
-				<frame_system::Pallet<T>>::extrinsic_index(),
+		/// Give `kitty_id` to `who` now, and its DNA once the reveal block is known.
+		pub(crate) fn request_birth(
+			who: &T::AccountId,
+			kitty_id: KittyId,
+			name: [u8; 8],
+			generation: u32,
+			parents_dna: Option<([u8; 16], [u8; 16])>,
+		) -> DispatchResult {
+			let reveal_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::RevealDelay::get());
+			BirthsRevealedAt::<T>::try_append(reveal_at, kitty_id)
+				.map_err(|_| Error::<T>::TooManyBirths)?;
+			PendingBirths::<T>::insert(
+				kitty_id,
+				PendingBirth { name, generation, parents_dna, reveal_at },
 			);
-			payload.using_encoded(blake2_128)
-		}
-
-		/// Get random value from parent kitty.
-		pub fn random_value_from_two_kitty(
-			owner: &T::AccountId,
-			kitty_1: Kitty,
-			kitty_2: Kitty,
-		) -> [u8; 16] {
-			let selector = Self::random_value(&owner);
-			let mut data = [0u8; 16];
-			for i in 0..kitty_1.dna.len() {
-				data[i] = (kitty_1.dna[i] & selector[i]) | (kitty_2.dna[i] & !selector[i]);
+			KittyOwner::<T>::insert(kitty_id, who);
+			Self::add_owned(who, kitty_id)?;
+
+			Self::deposit_event(Event::KittyRequested { owner: who.clone(), kitty_id, reveal_at });
+			Ok(())
+		}
+
+		/// Give DNA to the Kitties waiting for the hash of block `reveal_at`.
+		pub(crate) fn reveal_births(reveal_at: T::BlockNumber) -> Weight {
+			let kitty_ids = BirthsRevealedAt::<T>::take(reveal_at);
+			let weight = T::WeightInfo::reveal_births(kitty_ids.len() as u32);
+			if kitty_ids.is_empty() {
+				return weight
+			}
+
+			let hash = frame_system::Pallet::<T>::block_hash(reveal_at);
+			let now = frame_system::Pallet::<T>::block_number();
+			for kitty_id in kitty_ids {
+				let birth = match PendingBirths::<T>::take(kitty_id) {
+					Some(birth) => birth,
+					None => continue,
+				};
+
+				let seed = (hash, kitty_id).encode();
+				let dna = match birth.parents_dna {
+					Some((dna_1, dna_2)) =>
+						genetics::inherit(&dna_1, &dna_2, &seed, T::MutationRate::get()),
+					None => blake2_128(&seed),
+				};
+				let kitty = Kitty {
+					dna,
+					name: birth.name,
+					generation: birth.generation,
+					born_at: now,
+					cooldown_until: now,
+				};
+				Kitties::<T>::insert(kitty_id, &kitty);
+
+				if let Some(owner) = KittyOwner::<T>::get(kitty_id) {
+					let event = if birth.parents_dna.is_some() {
+						Event::KittyBreed { owner, kitty_id, kitty }
+					} else {
+						Event::KittyCreated { owner, kitty_id, kitty }
+					};
+					Self::deposit_event(event);
+				}
 			}
-			data
+			weight
+		}
+
+		/// Settle the auctions ending at block `end`.
+		pub(crate) fn settle_auctions(end: T::BlockNumber) -> Weight {
+			let kitty_ids = AuctionsEndingAt::<T>::take(end);
+			let weight = T::WeightInfo::settle_auctions(kitty_ids.len() as u32);
+			for kitty_id in kitty_ids {
+				if let Some(auction) = Auctions::<T>::take(kitty_id) {
+					Self::settle_auction(kitty_id, auction);
+				}
+			}
+			weight
+		}
+
+		/// Decoded genes of a Kitty.
+		pub fn traits(kitty_id: KittyId) -> Option<Vec<genetics::Trait>> {
+			Kitties::<T>::get(kitty_id).map(|kitty| genetics::decode(&kitty.dna))
+		}
+
+		/// A born Kitty with its owner and current listing.
+		pub fn kitty_info(kitty_id: KittyId) -> Option<KittyInfoOf<T>> {
+			let kitty = Kitties::<T>::get(kitty_id)?;
+			let owner = KittyOwner::<T>::get(kitty_id)?;
+			let listing =
+				KittyOnSale::<T>::get(kitty_id).filter(|listing| !Self::is_expired(listing));
+			Some(KittyInfo { kitty_id, kitty, owner, listing })
+		}
+
+		/// Up to `limit` born Kitties of `owner`, skipping the first `start`.
+		pub fn kitties_of(owner: &T::AccountId, start: u32, limit: u32) -> Vec<KittyInfoOf<T>> {
+			OwnedKitties::<T>::get(owner)
+				.into_iter()
+				.filter_map(Self::kitty_info)
+				.skip(start as usize)
+				.take(limit as usize)
+				.collect()
+		}
+
+		/// Ancestors of a Kitty up to `depth` generations back, parents first.
+		///
+		/// Released ancestors are included, `KittyParents` outlives them.
+		pub fn ancestors(kitty_id: KittyId, depth: u32) -> Vec<KittyId> {
+			let mut ancestors = Vec::new();
+			let mut generation = sp_std::vec![kitty_id];
+			for _ in 0..depth {
+				let mut parents = Vec::new();
+				for (parent_1, parent_2) in generation.iter().filter_map(KittyParents::<T>::get) {
+					for parent in [parent_1, parent_2] {
+						if !ancestors.contains(&parent) && !parents.contains(&parent) {
+							parents.push(parent);
+						}
+					}
+				}
+				if parents.is_empty() {
+					break
+				}
+				ancestors.extend_from_slice(&parents);
+				generation = parents;
+			}
+			ancestors
+		}
+
+		/// All descendants of a Kitty, in ID order.
+		///
+		/// This reads every `KittyParents` entry and is meant for runtime API calls only.
+		pub fn descendants(kitty_id: KittyId) -> Vec<KittyId> {
+			let mut children: Vec<(KittyId, (KittyId, KittyId))> =
+				KittyParents::<T>::iter().collect();
+			// parents always have lower IDs than their children
+			children.sort_unstable_by_key(|(child, _)| *child);
+
+			let mut family = BTreeSet::from([kitty_id]);
+			let mut descendants = Vec::new();
+			for (child, (parent_1, parent_2)) in children {
+				if family.contains(&parent_1) || family.contains(&parent_2) {
+					family.insert(child);
+					descendants.push(child);
+				}
+			}
+			descendants
+		}
+
+		/// Kitties with a listing that hasn't expired.
+		pub fn kitties_for_sale() -> Vec<KittyInfoOf<T>> {
+			KittyOnSale::<T>::iter_keys()
+				.filter_map(Self::kitty_info)
+				.filter(|info| info.listing.is_some())
+				.collect()
+		}
+
+		/// Check that `who` owns the existing Kitty `kitty_id`.
+		pub(crate) fn ensure_owner(who: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			let owner = KittyOwner::<T>::get(kitty_id).ok_or(Error::<T>::NoOwner)?;
+			ensure!(&owner == who, Error::<T>::NotOwner);
+			Ok(())
+		}
+
+		/// Check that `who` may breed with `kitty_id`.
+		///
+		/// Returns the owner and fee to pay when breeding uses a siring offer.
+		fn ensure_can_sire(
+			who: &T::AccountId,
+			kitty_id: KittyId,
+		) -> Result<Option<(T::AccountId, BalanceOf<T>)>, DispatchError> {
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			Self::ensure_unlocked(kitty_id)?;
+			let owner = KittyOwner::<T>::get(kitty_id).ok_or(Error::<T>::NoOwner)?;
+			if &owner == who {
+				return Ok(None)
+			}
+			let offer = SiringOffers::<T>::get(kitty_id)
+				.filter(|offer| &offer.allowed == who)
+				.ok_or(Error::<T>::NotOwner)?;
+			Ok(Some((owner, offer.fee)))
+		}
+
+		/// Move `kitty_id` to `new_owner`, dropping everything granted by the previous owner.
+		pub(crate) fn change_owner(kitty_id: KittyId, new_owner: &T::AccountId) -> DispatchResult {
+			if let Some(old_owner) = KittyOwner::<T>::get(kitty_id) {
+				OwnedKitties::<T>::mutate(&old_owner, |ids| ids.retain(|id| *id != kitty_id));
+			}
+			Self::add_owned(new_owner, kitty_id)?;
+			KittyOwner::<T>::insert(kitty_id, new_owner);
+			KittyOnSale::<T>::remove(kitty_id);
+			SiringOffers::<T>::remove(kitty_id);
+			Approvals::<T>::remove(kitty_id);
+			Ok(())
+		}
+
+		/// Whether `who` may submit prices.
+		pub fn is_oracle_authority(who: &T::AccountId) -> bool {
+			OracleAuthorities::<T>::get().contains(who)
+		}
+
+		/// Whether `symbol` is made of uppercase letters and digits only.
+		pub fn is_valid_symbol(symbol: &[u8]) -> bool {
+			!symbol.is_empty() &&
+				symbol.iter().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+		}
+
+		pub fn is_watched(symbol: &Symbol) -> bool {
+			Watchlist::<T>::get().contains(symbol)
+		}
+
+		/// Whether `record` is older than `PriceStaleAfter`.
+		pub fn is_stale(record: &PriceRecordOf<T>) -> bool {
+			frame_system::Pallet::<T>::block_number() >=
+				record.block.saturating_add(T::PriceStaleAfter::get())
+		}
+
+		/// Whether `who` submitted a price in the open round of `symbol`.
+		pub fn has_submitted(symbol: &Symbol, who: &T::AccountId) -> bool {
+			let now = frame_system::Pallet::<T>::block_number();
+			!Self::round_expired(&Rounds::<T>::get(symbol), now) &&
+				RoundSubmissions::<T>::get(symbol).iter().any(|(reporter, _)| reporter == who)
+		}
+
+		fn round_expired(round: &RoundOf<T>, now: T::BlockNumber) -> bool {
+			now >= round.started_at.saturating_add(T::RoundDuration::get())
+		}
+
+		/// Add a price to the open round of `symbol`, which closes once it reaches the quorum.
+		fn submit_price(
+			symbol: Symbol,
+			reporter: T::AccountId,
+			price: FixedU128,
+		) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut round = Rounds::<T>::get(&symbol);
+			let mut submissions = RoundSubmissions::<T>::get(&symbol);
+			if !submissions.is_empty() && Self::round_expired(&round, now) {
+				Self::deposit_event(Event::RoundExpired {
+					symbol: symbol.clone(),
+					round: round.id,
+				});
+				round.id = round.id.wrapping_add(1);
+				submissions = Default::default();
+			}
+			if submissions.is_empty() {
+				round.started_at = now;
+			}
+
+			ensure!(
+				!submissions.iter().any(|(who, _)| who == &reporter),
+				Error::<T>::AlreadySubmitted
+			);
+			submissions
+				.try_push((reporter.clone(), price))
+				.map_err(|_| Error::<T>::TooManyOracleAuthorities)?;
+			Self::deposit_event(Event::PriceSubmitted {
+				symbol: symbol.clone(),
+				round: round.id,
+				reporter,
+				price,
+			});
+
+			if submissions.len() as u32 >= T::OracleQuorum::get().max(1) {
+				Self::finalize_round(&symbol, round.id, &submissions, now);
+				round.id = round.id.wrapping_add(1);
+				RoundSubmissions::<T>::remove(&symbol);
+			} else {
+				RoundSubmissions::<T>::insert(&symbol, submissions);
+			}
+			Rounds::<T>::insert(&symbol, round);
+			Ok(())
+		}
+
+		/// Store the median of `submissions` as the price of `symbol`.
+		fn finalize_round(
+			symbol: &Symbol,
+			round: RoundId,
+			submissions: &[(T::AccountId, FixedU128)],
+			now: T::BlockNumber,
+		) {
+			let mut prices: Vec<FixedU128> = submissions.iter().map(|(_, price)| *price).collect();
+			prices.sort();
+			let price = Self::median(&prices);
+
+			let max_deviation = T::MaxPriceDeviation::get().mul_floor(price.into_inner());
+			let outliers = submissions
+				.iter()
+				.filter(|(_, submitted)| {
+					let (a, b) = (submitted.into_inner(), price.into_inner());
+					a.max(b) - a.min(b) > max_deviation
+				})
+				.map(|(who, _)| who.clone())
+				.collect();
+
+			Prices::<T>::insert(
+				symbol,
+				PriceRecord {
+					price,
+					round,
+					reporters: submissions.len() as u32,
+					block: now,
+					timestamp: T::UnixTime::now().as_millis().unique_saturated_into(),
+				},
+			);
+			Self::deposit_event(Event::RoundFinalized {
+				symbol: symbol.clone(),
+				round,
+				price,
+				outliers,
+			});
+		}
+
+		/// Median of sorted, non-empty `prices`, the mean of the middle two for an even count.
+		pub(crate) fn median(prices: &[FixedU128]) -> FixedU128 {
+			let middle = prices.len() / 2;
+			if prices.len() % 2 == 1 {
+				return prices[middle]
+			}
+			let (a, b) = (prices[middle - 1].into_inner(), prices[middle].into_inner());
+			FixedU128::from_inner(a / 2 + b / 2 + (a % 2 + b % 2) / 2)
+		}
+
+		/// Whether `operator` may transfer and approve every Kitty of `owner`.
+		pub fn is_approved_for_all(owner: &T::AccountId, operator: &T::AccountId) -> bool {
+			OperatorApprovals::<T>::contains_key(owner, operator)
+		}
+
+		/// Whether `who` may transfer `kitty_id`, as its owner or an approved operator.
+		pub fn is_approved(who: &T::AccountId, kitty_id: KittyId) -> bool {
+			match KittyOwner::<T>::get(kitty_id) {
+				Some(owner) =>
+					&owner == who ||
+						Approvals::<T>::get(kitty_id).as_ref() == Some(who) ||
+						Self::is_approved_for_all(&owner, who),
+				None => false,
+			}
+		}
+
+		/// Remove a Kitty of `owner`, unreserving its open offers. `KittyParents` is kept.
+		pub(crate) fn remove_kitty(owner: &T::AccountId, kitty_id: KittyId) {
+			for offer in Offers::<T>::take(kitty_id) {
+				T::Currency::unreserve(&offer.buyer, offer.amount);
+			}
+			OwnedKitties::<T>::mutate(owner, |ids| ids.retain(|id| *id != kitty_id));
+			Kitties::<T>::remove(kitty_id);
+			KittyOwner::<T>::remove(kitty_id);
+			KittyOnSale::<T>::remove(kitty_id);
+			SiringOffers::<T>::remove(kitty_id);
+			Approvals::<T>::remove(kitty_id);
+			KittyDeposits::<T>::remove(kitty_id);
+		}
+
+		pub(crate) fn add_owned(who: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			OwnedKitties::<T>::try_append(who, kitty_id).map_err(|_| Error::<T>::TooManyKitties)?;
+			Ok(())
+		}
+
+		pub(crate) fn ensure_unlocked(kitty_id: KittyId) -> DispatchResult {
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyLocked);
+			Ok(())
+		}
+
+		/// Current price of a Dutch auction, decaying linearly to the reserve price at its end.
+		pub fn dutch_price(auction: &AuctionOf<T>) -> BalanceOf<T> {
+			let start_price = match auction.kind {
+				AuctionKind::Dutch { start_price } => start_price,
+				AuctionKind::English => return auction.reserve_price,
+			};
+			let now = frame_system::Pallet::<T>::block_number();
+			let elapsed: u32 = now.saturating_sub(auction.start).unique_saturated_into();
+			let duration: u32 = auction.end.saturating_sub(auction.start).unique_saturated_into();
+			let decay = Perbill::from_rational(elapsed.min(duration), duration.max(1));
+			start_price.saturating_sub(decay * start_price.saturating_sub(auction.reserve_price))
+		}
+
+		fn remove_ending(kitty_id: KittyId, end: T::BlockNumber) {
+			AuctionsEndingAt::<T>::mutate(end, |ids| ids.retain(|id| *id != kitty_id));
+		}
+
+		/// Hand an ended auction to its best bidder, or back to the seller if there is none.
+		fn settle_auction(kitty_id: KittyId, auction: AuctionOf<T>) {
+			let seller = auction.seller;
+			if let Some((buyer, price)) = auction.best_bid {
+				// hooks aren't transactional, a failed settlement must not leave a partial update
+				let settled = with_storage_layer(|| -> DispatchResult {
+					let remaining = T::Currency::repatriate_reserved(
+						&buyer,
+						&seller,
+						price,
+						BalanceStatus::Free,
+					)?;
+					ensure!(remaining.is_zero(), Error::<T>::BidNotFunded);
+					Self::change_owner(kitty_id, &buyer)
+				});
+				match settled {
+					Ok(()) =>
+						Self::deposit_event(Event::AuctionWon { seller, buyer, kitty_id, price }),
+					Err(e) => {
+						log::error!("Failed to settle auction of kitty {}: {:?}", kitty_id, e);
+						T::Currency::unreserve(&buyer, price);
+						Self::deposit_event(Event::AuctionFailed { seller, buyer, kitty_id });
+					},
+				}
+				return
+			}
+			Self::deposit_event(Event::AuctionExpired { seller, kitty_id });
+		}
+
+		/// Drop expired offers, returning their funds to the buyers.
+		fn prune_expired_offers(offers: &mut BoundedVec<OfferOf<T>, T::MaxOffersPerKitty>) {
+			let now = frame_system::Pallet::<T>::block_number();
+			offers.retain(|offer| {
+				if offer.expires_at > now {
+					return true
+				}
+				T::Currency::unreserve(&offer.buyer, offer.amount);
+				false
+			});
+		}
+
+		fn is_expired(listing: &ListingOf<T>) -> bool {
+			listing
+				.expires_at
+				.map_or(false, |expires_at| frame_system::Pallet::<T>::block_number() >= expires_at)
+		}
+
+		/// Breeding cooldown of a Kitty of `generation`.
+		pub fn cooldown(generation: u32) -> T::BlockNumber {
+			T::BreedingCooldown::get().saturating_mul(generation.saturating_add(1).into())
 		}
 
 		fn get_pallet_account_id() -> T::AccountId {
 			T::PalletId::get().into_account_truncating()
 		}
 
-		fn fetch_coin_price_info() -> Result<CoinPriceInfo, http::Error> {
+		/// Fetch the price of `symbol` from the first active source that serves it.
+		pub(crate) fn fetch_price(symbol: &[u8]) -> Option<FixedU128> {
+			for source in price_source::active_sources() {
+				match Self::fetch_price_from(source, symbol) {
+					Ok(price) => return Some(price),
+					Err(err) => log::warn!("OCW ==> {} failed: {:?}", source.name(), err),
+				}
+			}
+			None
+		}
+
+		fn fetch_price_from(
+			source: &dyn PriceSource,
+			symbol: &[u8],
+		) -> Result<FixedU128, http::Error> {
+			let url = source.url(symbol).ok_or(http::Error::Unknown)?;
+			let url = sp_std::str::from_utf8(&url).map_err(|_| http::Error::Unknown)?;
 			// prepare for send request
 			let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(118_000));
-			let request =
-				http::Request::get("https://data.binance.com/api/v3/avgPrice?symbol=BTCUSDT");
+			let request = http::Request::get(url);
 			let pending = request
 				.add_header("User-Agent", "Substrate-Offchain-Worker")
 				.deadline(deadline)
@@ -463,44 +1979,23 @@ pub mod pallet {
 				return Err(http::Error::Unknown)
 			}
 			let body = response.body().collect::<Vec<u8>>();
-			let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
-				log::warn!("No UTF8 body");
-				http::Error::Unknown
-			})?;
-
-			// parse the response str
-			let coin_price: CoinPriceInfo =
-				serde_json::from_str(body_str).map_err(|_| http::Error::Unknown)?;
 
-			Ok(coin_price)
+			// parse the response body
+			source.parse(&body).ok_or_else(|| {
+				log::warn!("Unexpected response body");
+				http::Error::Unknown
+			})
 		}
+	}
 
-		fn fetch_coin_info() -> BoundedVec<u8, ConstU32<3>> {
-			let mut res = BoundedVec::<u8, ConstU32<3>>::try_from(b"BTC".to_vec()).unwrap();
-			let coin_data_option: Option<Vec<u8>> = sp_io::offchain::local_storage_get(
-				sp_core::offchain::StorageKind::PERSISTENT,
-				&ON_CHAIN_KEY,
-			);
+	impl<T: Config> PriceProvider<T::BlockNumber> for Pallet<T> {
+		type Price = FixedU128;
 
-			if let Some(coin_data_vec) = coin_data_option {
-				let coin_data = BoundedVec::<u8, ConstU32<3>>::decode(&mut &coin_data_vec[..]);
-				match coin_data {
-					Ok(coin_data) => {
-						log::info!("OCW ==> got key: {:?}", ON_CHAIN_KEY);
-						log::info!(
-							"OCW ==> got value: {:?}",
-							sp_std::str::from_utf8(&coin_data).unwrap()
-						);
-						res = coin_data;
-					},
-					Err(_) => {
-						log::error!("OCW ==> Failed to decode offchain coin data");
-					},
-				};
-			} else {
-				log::warn!("OCW ==> No coin data in offchain local storage");
-			}
-			res
+		fn latest_price(symbol: &[u8]) -> Option<(FixedU128, T::BlockNumber)> {
+			let symbol = Symbol::try_from(symbol.to_vec()).ok()?;
+			Prices::<T>::get(symbol)
+				.filter(|record| !Self::is_stale(record))
+				.map(|record| (record.price, record.block))
 		}
 	}
 
@@ -529,7 +2024,19 @@ pub mod pallet {
 					if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
 						return InvalidTransaction::BadProof.into()
 					}
-					valid_tx(b"unsigned_extrinsic_with_signed_payload".to_vec())
+					let reporter = payload.public.clone().into_account();
+					if !Self::is_oracle_authority(&reporter) {
+						return InvalidTransaction::BadSigner.into()
+					}
+					if !Self::is_watched(&payload.symbol) {
+						return InvalidTransaction::Call.into()
+					}
+					// one submission per authority and round
+					if Self::has_submitted(&payload.symbol, &reporter) {
+						return InvalidTransaction::Stale.into()
+					}
+					let round = Rounds::<T>::get(&payload.symbol).id;
+					valid_tx((&payload.symbol, reporter, round).encode())
 				},
 				_ => InvalidTransaction::Call.into(),
 			}
@@ -1,2 +1,3 @@
 // pub mod v1;
-pub mod v2;
\ No newline at end of file
+pub mod v2;
+pub mod v3;
\ No newline at end of file
@@ -1,7 +1,11 @@
-use crate::{Config, Kitties, Kitty, KittyId, Pallet};
+use crate::{Config, Kitties, KittyId, Pallet};
 use frame_support::{
-	migration::storage_key_iter, pallet_prelude::*, storage::StoragePrefixedMap,
-	traits::GetStorageVersion, weights::Weight, Blake2_128Concat,
+	migration::{put_storage_value, storage_key_iter},
+	pallet_prelude::*,
+	storage::StoragePrefixedMap,
+	traits::GetStorageVersion,
+	weights::Weight,
+	Blake2_128Concat, StorageHasher,
 };
 
 #[derive(
@@ -15,6 +19,20 @@ pub struct V1Kitty {
 	pub dna: [u8; 16],
 	pub name: [u8; 4],
 }
+#[derive(
+	Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct V2Kitty {
+	pub dna: [u8; 16],
+	pub name: [u8; 8],
+}
+
+// kitties are written in the v2 layout, later migrations convert them further
+fn put_kitty<T: Config>(index: KittyId, kitty: &V2Kitty) {
+	let module = Kitties::<T>::module_prefix();
+	let item = Kitties::<T>::storage_prefix();
+	put_storage_value(module, item, &Blake2_128Concat::hash(&index.encode()), kitty);
+}
 
 pub fn migrate<T: Config>() -> Weight {
 	let on_chain_version = Pallet::<T>::on_chain_storage_version();
@@ -23,11 +41,11 @@ pub fn migrate<T: Config>() -> Weight {
 	log::info!("---------------------on_chain_version:{:?}------------------", on_chain_version);
 	log::info!("---------------------current_version:{:?}------------------", current_version);
 
-	// works for version 1 to 2 or version 0 to 2
+	// works for version 1 to 2 or version 0 to 2, later versions build on top of it
 	if on_chain_version != 1 && on_chain_version != 0 {
 		return Weight::zero()
 	}
-	if current_version != 2 {
+	if current_version < 2 {
 		return Weight::zero()
 	}
 
@@ -42,8 +60,8 @@ pub fn migrate<T: Config>() -> Weight {
 			// using the first eight dna to be default name
 			let mut name: [u8; 8] = [0; 8];
 			name.clone_from_slice(&kitty.0[..8]);
-			let new_kitty = Kitty { dna: kitty.0, name };
-			Kitties::<T>::insert(index, &new_kitty);
+			let new_kitty = V2Kitty { dna: kitty.0, name };
+			put_kitty::<T>(index, &new_kitty);
 		}
 	}
 
@@ -56,8 +74,8 @@ pub fn migrate<T: Config>() -> Weight {
 			let mut name = [0u8; 8];
 			name[..4].copy_from_slice(&kitty.name);
 			name[4..].copy_from_slice(&kitty.name);
-			let new_kitty = Kitty { dna: kitty.dna, name };
-			Kitties::<T>::insert(index, &new_kitty);
+			let new_kitty = V2Kitty { dna: kitty.dna, name };
+			put_kitty::<T>(index, &new_kitty);
 		}
 	}
 
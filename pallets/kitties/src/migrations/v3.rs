@@ -0,0 +1,82 @@
+use super::v2::V2Kitty;
+use crate::{
+	Config, Kitties, Kitty, KittyDeposits, KittyId, KittyOnSale, KittyOwner, KittyParents,
+	KittyPrice, Listing, OwnedKitties, Pallet,
+};
+use frame_support::{
+	migration::storage_key_iter, pallet_prelude::*, storage::StoragePrefixedMap,
+	traits::GetStorageVersion, weights::Weight, Blake2_128Concat,
+};
+use sp_runtime::traits::Zero;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+	let current_version = Pallet::<T>::current_storage_version();
+
+	log::info!("---------------------on_chain_version:{:?}------------------", on_chain_version);
+	log::info!("---------------------current_version:{:?}------------------", current_version);
+
+	// works for any version before 3, kitties are upgraded by v2 first
+	if on_chain_version >= 3 {
+		return Weight::zero()
+	}
+	if current_version < 3 {
+		return Weight::zero()
+	}
+
+	// listings used to be `()` and were bought at the global kitty price
+	let price = KittyPrice::<T>::get();
+	let mut translated = 0u64;
+	KittyOnSale::<T>::translate::<(), _>(|_, ()| {
+		translated += 1;
+		Some(Listing { price, expires_at: None })
+	});
+
+	// parents always have lower ids, so generations can be computed in id order
+	let module = Kitties::<T>::module_prefix();
+	let item = Kitties::<T>::storage_prefix();
+	let mut kitties: Vec<(KittyId, V2Kitty)> =
+		storage_key_iter::<KittyId, V2Kitty, Blake2_128Concat>(module, item)
+			.drain()
+			.collect();
+	kitties.sort_by_key(|(index, _)| *index);
+
+	let mut generations = BTreeMap::<KittyId, u32>::new();
+	let converted = kitties.len() as u64;
+	for (index, kitty) in kitties {
+		let generation = KittyParents::<T>::get(index)
+			.map(|(parent_1, parent_2)| {
+				let generation_of = |parent| generations.get(&parent).copied().unwrap_or_default();
+				generation_of(parent_1).max(generation_of(parent_2)).saturating_add(1)
+			})
+			.unwrap_or_default();
+		generations.insert(index, generation);
+
+		// the birth block is unknown, treat existing kitties as born at genesis and rested
+		let new_kitty = Kitty {
+			dna: kitty.dna,
+			name: kitty.name,
+			generation,
+			born_at: Zero::zero(),
+			cooldown_until: Zero::zero(),
+		};
+		Kitties::<T>::insert(index, &new_kitty);
+		// the price paid is unknown too, release refunds a share of the price before the upgrade
+		KittyDeposits::<T>::insert(index, price);
+	}
+
+	// build the owner index, which didn't exist before
+	let mut indexed = 0u64;
+	for (kitty_id, owner) in KittyOwner::<T>::iter() {
+		if OwnedKitties::<T>::try_append(&owner, kitty_id).is_err() {
+			log::warn!("kitty {} not indexed, its owner has too many kitties", kitty_id);
+		}
+		indexed += 1;
+	}
+
+	T::DbWeight::get().reads_writes(
+		translated + converted * 2 + indexed * 2,
+		translated + converted * 3 + indexed,
+	)
+}
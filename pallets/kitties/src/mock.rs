@@ -1,16 +1,16 @@
 use crate as pallet_kitties;
 use frame_support::{
 	parameter_types,
-	traits::{ConstU16, ConstU64},
+	traits::{ConstU16, ConstU32, ConstU64},
 	PalletId,
 };
 use frame_system as system;
 use pallet_balances;
-use pallet_randomness_collective_flip;
 use sp_core::{ConstU128, H256};
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
+	Percent, Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -25,8 +25,8 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system,
 		KittiesModule: pallet_kitties,
-		Randomness: pallet_randomness_collective_flip,
 		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
 	}
 );
 
@@ -65,18 +65,75 @@ pub const EXISTENTIAL_DEPOSIT: u128 = 1;
 
 parameter_types! {
 	pub KittyPalletId: PalletId = PalletId(*b"py/kitty");
-	pub KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub DefaultKittyPrice: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub MutationRate: Permill = Permill::from_percent(1);
+	pub ReleaseRefund: Percent = Percent::from_percent(50);
+	pub MaxPriceDeviation: Permill = Permill::from_percent(5);
 }
 
 impl pallet_kitties::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
-	type Randomness = Randomness;
 	type Currency = Balances;
-	type KittyPrice = KittyPrice;
+	type DefaultKittyPrice = DefaultKittyPrice;
 	type PalletId = KittyPalletId;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type MaxAuctionsPerBlock = ConstU32<2>;
+	type MaxAuctionDuration = ConstU64<100>;
+	type MaxOffersPerKitty = ConstU32<2>;
+	type MaxKittiesOwned = ConstU32<3>;
+	type BreedingCooldown = ConstU64<10>;
+	type RevealDelay = ConstU64<2>;
+	type MaxBirthsPerBlock = ConstU32<4>;
+	type ReleaseRefund = ReleaseRefund;
+	type MutationRate = MutationRate;
+	type AuthorityId = MockAuthId;
+	type UnixTime = Timestamp;
+	type MaxOracleAuthorities = ConstU32<3>;
+	type OracleQuorum = ConstU32<3>;
+	type RoundDuration = ConstU64<5>;
+	type PriceStaleAfter = ConstU64<10>;
+	type MaxPriceDeviation = MaxPriceDeviation;
+	type MaxWatchedSymbols = ConstU32<3>;
+	type WeightInfo = ();
+}
+
+/// Signs offchain worker payloads with the `UintAuthorityId` test keys.
+pub struct MockAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for MockAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+	type GenericPublic = UintAuthorityId;
+}
+
+type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
 }
 
-impl pallet_randomness_collective_flip::Config for Test {}
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(RuntimeCall, (u64, ()))> {
+		Some((call, (nonce, ())))
+	}
+}
 
 impl pallet_balances::Config for Test {
 	/// The type for recording an account's balance.
@@ -92,13 +149,37 @@ impl pallet_balances::Config for Test {
 	type ReserveIdentifier = [u8; 8];
 }
 
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<5>;
+	type WeightInfo = ();
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut ext = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	// set initial balance, user 3‘balance is too low to do a transaction
-	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 10_000_000), (2, 10_000_000), (3, 5)] }
-		.assimilate_storage(&mut ext)
-		.unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10_000_000), (2, 10_000_000), (3, 5)],
+	}
+	.assimilate_storage(&mut ext)
+	.unwrap();
+	pallet_kitties::GenesisConfig::<Test> {
+		oracle_authorities: vec![7, 8],
+		watchlist: vec![b"BTCUSDT".to_vec()],
+	}
+	.assimilate_storage(&mut ext)
+	.unwrap();
 	let mut ext: sp_io::TestExternalities = ext.into();
 	ext.execute_with(|| System::set_block_number(1));
 	ext
 }
+
+/// Reveal every pending birth, with made up hashes for the reveal blocks.
+pub fn reveal_births() {
+	let blocks: Vec<u64> = pallet_kitties::BirthsRevealedAt::<Test>::iter_keys().collect();
+	for block in blocks {
+		frame_system::BlockHash::<Test>::insert(block, H256::from_low_u64_be(block));
+		KittiesModule::reveal_births(block);
+	}
+}
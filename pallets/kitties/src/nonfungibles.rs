@@ -0,0 +1,130 @@
+//! Implementations of the FRAME `nonfungibles` traits, so other pallets can trade Kitties.
+//!
+//! All Kitties belong to the single collection `()` and are identified by their `KittyId`. Their
+//! DNA and name are exposed as the `dna` and `name` attributes.
+//!
+//! Minted Kitties skip the commit-reveal of `create_kitty`, see `Mutate::mint_into`.
+
+use crate::{
+	Config, Error, Event, Kitties, Kitty, KittyId, KittyOwner, NextKittyId, OwnedKitties, Pallet,
+};
+use codec::Encode;
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::tokens::nonfungibles::{Inspect, InspectEnumerable, Mutate, Transfer},
+};
+use sp_io::hashing::blake2_128;
+use sp_std::{boxed::Box, vec::Vec};
+
+/// Attribute key of the Kitty DNA.
+pub const DNA_KEY: &[u8] = b"dna";
+/// Attribute key of the Kitty name.
+pub const NAME_KEY: &[u8] = b"name";
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type ItemId = KittyId;
+	type CollectionId = ();
+
+	fn owner(_collection: &(), item: &KittyId) -> Option<T::AccountId> {
+		// Kitties waiting for their reveal block don't exist yet
+		Kitties::<T>::contains_key(item).then(|| KittyOwner::<T>::get(item)).flatten()
+	}
+
+	fn attribute(_collection: &(), item: &KittyId, key: &[u8]) -> Option<Vec<u8>> {
+		let kitty = Kitties::<T>::get(item)?;
+		match key {
+			DNA_KEY => Some(kitty.dna.to_vec()),
+			NAME_KEY => Some(kitty.name.to_vec()),
+			_ => None,
+		}
+	}
+
+	fn can_transfer(_collection: &(), item: &KittyId) -> bool {
+		Kitties::<T>::contains_key(item) && Self::ensure_unlocked(*item).is_ok()
+	}
+}
+
+impl<T: Config> InspectEnumerable<T::AccountId> for Pallet<T> {
+	fn collections() -> Box<dyn Iterator<Item = ()>> {
+		Box::new(sp_std::iter::once(()))
+	}
+
+	fn items(_collection: &()) -> Box<dyn Iterator<Item = KittyId>> {
+		Box::new(Kitties::<T>::iter_keys())
+	}
+
+	fn owned(who: &T::AccountId) -> Box<dyn Iterator<Item = ((), KittyId)>> {
+		Box::new(Self::owned_in_collection(&(), who).map(|item| ((), item)))
+	}
+
+	fn owned_in_collection(
+		_collection: &(),
+		who: &T::AccountId,
+	) -> Box<dyn Iterator<Item = KittyId>> {
+		let items: Vec<KittyId> = OwnedKitties::<T>::get(who)
+			.into_iter()
+			.filter(|item| Kitties::<T>::contains_key(item))
+			.collect();
+		Box::new(items.into_iter())
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(_collection: &(), item: &KittyId, destination: &T::AccountId) -> DispatchResult {
+		let owner = Self::owner(&(), item).ok_or(Error::<T>::InvalidKittyId)?;
+		Self::ensure_unlocked(*item)?;
+
+		Self::change_owner(*item, destination)?;
+		Self::deposit_event(Event::KittyTransferred {
+			owner,
+			recipient: destination.clone(),
+			kitty_id: *item,
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+	/// Mint the next Kitty ID to `who`, free of charge and unnamed.
+	///
+	/// Unlike created Kitties, it is born right away so that `owner` is set on return, as
+	/// `nonfungibles` callers expect. Its DNA comes from the parent block hash rather than a
+	/// later reveal block, so it is predictable: only expose minting to trusted origins.
+	fn mint_into(_collection: &(), item: &KittyId, who: &T::AccountId) -> DispatchResult {
+		ensure!(*item == NextKittyId::<T>::get(), Error::<T>::InvalidKittyId);
+		let kitty_id = Self::get_next_id()?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let seed = (frame_system::Pallet::<T>::parent_hash(), kitty_id).encode();
+		let kitty = Kitty {
+			dna: blake2_128(&seed),
+			name: Default::default(),
+			generation: 0,
+			born_at: now,
+			cooldown_until: now,
+		};
+		KittyOwner::<T>::insert(kitty_id, who);
+		Self::add_owned(who, kitty_id)?;
+		Kitties::<T>::insert(kitty_id, &kitty);
+
+		Self::deposit_event(Event::KittyCreated { owner: who.clone(), kitty_id, kitty });
+		Ok(())
+	}
+
+	fn burn(
+		_collection: &(),
+		item: &KittyId,
+		maybe_check_owner: Option<&T::AccountId>,
+	) -> DispatchResult {
+		let owner = Self::owner(&(), item).ok_or(Error::<T>::InvalidKittyId)?;
+		if let Some(check_owner) = maybe_check_owner {
+			ensure!(check_owner == &owner, Error::<T>::NotOwner);
+		}
+		Self::ensure_unlocked(*item)?;
+
+		Self::remove_kitty(&owner, *item);
+		Self::deposit_event(Event::KittyBurned { owner, kitty_id: *item });
+		Ok(())
+	}
+}
@@ -0,0 +1,152 @@
+//! Exchange APIs the offchain worker fetches prices from.
+
+use crate::coin_price::{de_string_to_price, parse_price, CoinPriceInfo};
+use frame_support::inherent::Vec;
+use serde::Deserialize;
+use sp_core::offchain::StorageKind;
+use sp_runtime::FixedU128;
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Offchain local storage key of the active sources, as their comma separated names in order
+/// of preference. All sources are used when it is unset.
+pub const SOURCES_KEY: &[u8] = b"kitties::price-sources";
+
+/// Every source, in the default order of preference.
+pub const SOURCES: [&dyn PriceSource; 3] = [&Binance, &Coinbase, &Kraken];
+
+/// Quote currencies recognised at the end of a trading pair, `USDT` before `USD`.
+const QUOTES: [&[u8]; 8] = [b"USDT", b"USDC", b"BUSD", b"USD", b"EUR", b"GBP", b"BTC", b"ETH"];
+
+/// An exchange API serving the price of a trading pair.
+pub trait PriceSource {
+	/// The name selecting the source, e.g. `binance`.
+	fn name(&self) -> &'static str;
+
+	/// The URL of the price of `symbol`, a pair such as `BTCUSDT`.
+	///
+	/// `None` if the pair can't be expressed for this exchange.
+	fn url(&self, symbol: &[u8]) -> Option<Vec<u8>>;
+
+	/// Parse the price out of a response body.
+	fn parse(&self, body: &[u8]) -> Option<FixedU128>;
+}
+
+/// Average price over the last minutes, `{"mins":5,"price":"27000.50"}`.
+pub struct Binance;
+
+impl PriceSource for Binance {
+	fn name(&self) -> &'static str {
+		"binance"
+	}
+
+	fn url(&self, symbol: &[u8]) -> Option<Vec<u8>> {
+		Some([&b"https://data.binance.com/api/v3/avgPrice?symbol="[..], symbol].concat())
+	}
+
+	fn parse(&self, body: &[u8]) -> Option<FixedU128> {
+		serde_json::from_slice::<CoinPriceInfo>(body).ok().map(|info| info.price)
+	}
+}
+
+/// Last trade of a product, `{"price":"27000.50",...}`. Products are named `BTC-USDT`.
+pub struct Coinbase;
+
+#[derive(Deserialize)]
+struct CoinbaseTicker {
+	#[serde(deserialize_with = "de_string_to_price")]
+	price: FixedU128,
+}
+
+impl PriceSource for Coinbase {
+	fn name(&self) -> &'static str {
+		"coinbase"
+	}
+
+	fn url(&self, symbol: &[u8]) -> Option<Vec<u8>> {
+		let (base, quote) = split_pair(symbol)?;
+		Some(
+			[&b"https://api.exchange.coinbase.com/products/"[..], base, b"-", quote, b"/ticker"]
+				.concat(),
+		)
+	}
+
+	fn parse(&self, body: &[u8]) -> Option<FixedU128> {
+		serde_json::from_slice::<CoinbaseTicker>(body).ok().map(|ticker| ticker.price)
+	}
+}
+
+/// Ticker of a pair, `{"error":[],"result":{"XBTUSDT":{"c":["27000.50","0.1"],...}}}`.
+pub struct Kraken;
+
+#[derive(Deserialize)]
+struct KrakenResponse<'a> {
+	#[serde(borrow)]
+	error: Vec<&'a str>,
+	#[serde(borrow, default)]
+	result: BTreeMap<&'a str, KrakenTicker<'a>>,
+}
+
+#[derive(Deserialize)]
+struct KrakenTicker<'a> {
+	/// The last trade, as `[price, volume]`.
+	#[serde(borrow)]
+	c: Vec<&'a str>,
+}
+
+impl PriceSource for Kraken {
+	fn name(&self) -> &'static str {
+		"kraken"
+	}
+
+	fn url(&self, symbol: &[u8]) -> Option<Vec<u8>> {
+		Some([&b"https://api.kraken.com/0/public/Ticker?pair="[..], symbol].concat())
+	}
+
+	fn parse(&self, body: &[u8]) -> Option<FixedU128> {
+		let response: KrakenResponse = serde_json::from_slice(body).ok()?;
+		if !response.error.is_empty() {
+			return None
+		}
+		// the only pair requested, under Kraken's own name for it
+		let ticker = response.result.values().next()?;
+		parse_price(ticker.c.first()?.as_bytes()).ok()
+	}
+}
+
+/// The source named `name`.
+pub fn source(name: &str) -> Option<&'static dyn PriceSource> {
+	SOURCES.iter().copied().find(|source| source.name() == name)
+}
+
+/// The sources set under `SOURCES_KEY`, or every source if none is set.
+pub(crate) fn active_sources() -> Vec<&'static dyn PriceSource> {
+	let names = match sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, SOURCES_KEY) {
+		Some(names) => names,
+		None => return SOURCES.to_vec(),
+	};
+
+	let sources: Vec<_> = names
+		.split(|b| *b == b',')
+		.filter_map(|name| {
+			let name = sp_std::str::from_utf8(name).ok()?.trim();
+			let found = source(name);
+			if found.is_none() {
+				log::warn!("OCW ==> unknown price source: {}", name);
+			}
+			found
+		})
+		.collect();
+	if sources.is_empty() {
+		log::warn!("OCW ==> no known price source set, using all of them");
+		return SOURCES.to_vec()
+	}
+	sources
+}
+
+/// Split a pair such as `BTCUSDT` into its base and quote currencies.
+fn split_pair(symbol: &[u8]) -> Option<(&[u8], &[u8])> {
+	QUOTES.iter().find_map(|quote| match symbol.strip_suffix(*quote) {
+		Some(base) if !base.is_empty() => Some((base, *quote)),
+		_ => None,
+	})
+}
@@ -1,12 +1,40 @@
 use super::*;
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	coin_price::{parse_price, CoinPriceInfo, PriceError},
+	genetics::{decode, inherit, Dna, Gene, Trait},
+	mock::*,
+	price_source::{self, Binance, Coinbase, Kraken, PriceSource},
+	Error, PriceProvider,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		tokens::nonfungibles::{Inspect, InspectEnumerable, Mutate, Transfer},
+		Currency, GetStorageVersion, Hooks, ReservableCurrency, StorageVersion,
+	},
+	unsigned::ValidateUnsigned,
+};
+use sp_core::{
+	offchain::{
+		testing::{PendingRequest, TestOffchainExt},
+		OffchainDbExt, OffchainWorkerExt, StorageKind,
+	},
+	H256,
+};
+use sp_io::hashing::blake2_128;
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128,
+};
 
 #[test]
 fn create_kitty_works() {
 	new_test_ext().execute_with(|| {
 		assert_eq!(KittiesModule::next_kitty_id(), 0);
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
 		assert_eq!(KittiesModule::next_kitty_id(), 1);
 		assert!(KittiesModule::kitties(0).is_some());
 		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
@@ -46,12 +74,97 @@ fn create_kitty_overflow_fails() {
 	});
 }
 
+#[test]
+fn kitty_is_born_after_reveal_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		mock::System::assert_last_event(
+			Event::KittyRequested { owner: 1, kitty_id: 0, reveal_at: 3 }.into(),
+		);
+		assert_eq!(KittiesModule::kitties(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(KittiesModule::owned_kitties(1).into_inner(), vec![0]);
+		assert_eq!(KittiesModule::pending_birth(0).map(|birth| birth.reveal_at), Some(3));
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 0),
+			Error::<Test>::InvalidKittyId
+		);
+
+		// the hash of the reveal block is not known at its start
+		System::set_block_number(3);
+		KittiesModule::on_initialize(3);
+		assert_eq!(KittiesModule::kitties(0), None);
+
+		let hash = H256::repeat_byte(7);
+		frame_system::BlockHash::<Test>::insert(3, hash);
+		System::set_block_number(4);
+		KittiesModule::on_initialize(4);
+		let kitty = KittiesModule::kitties(0).unwrap();
+		assert_eq!(kitty.dna, blake2_128(&(hash, 0 as KittyId).encode()));
+		assert_eq!((kitty.generation, kitty.born_at, kitty.cooldown_until), (0, 4, 4));
+		assert_eq!(KittiesModule::pending_birth(0), None);
+		assert!(KittiesModule::births_revealed_at(3).is_empty());
+		mock::System::assert_last_event(
+			Event::KittyCreated { owner: 1, kitty_id: 0, kitty }.into(),
+		);
+	});
+}
+
+#[test]
+fn bred_kitty_inherits_parents_dna_at_request() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		let dna_1 = KittiesModule::kitties(0).unwrap().dna;
+		let dna_2 = KittiesModule::kitties(1).unwrap().dna;
+
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"));
+		assert_eq!(
+			KittiesModule::pending_birth(2),
+			Some(PendingBirth {
+				name: *b"testtest",
+				generation: 1,
+				parents_dna: Some((dna_1, dna_2)),
+				reveal_at: 3,
+			})
+		);
+
+		reveal_births();
+		let seed = (H256::from_low_u64_be(3), 2 as KittyId).encode();
+		assert_eq!(
+			KittiesModule::kitties(2).unwrap().dna,
+			inherit(&dna_1, &dna_2, &seed, MutationRate::get())
+		);
+	});
+}
+
+#[test]
+fn too_many_births_fails() {
+	new_test_ext().execute_with(|| {
+		// at most four births per reveal block in the mock runtime
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		}
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+		assert_noop!(
+			KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"),
+			Error::<Test>::TooManyBirths
+		);
+
+		System::set_block_number(2);
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+	});
+}
+
 #[test]
 fn breed_kitty_works() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
 		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"));
+		reveal_births();
 
 		mock::System::assert_last_event(
 			Event::KittyBreed { owner: 1, kitty_id: 2, kitty: KittiesModule::kitties(2).unwrap() }
@@ -65,11 +178,62 @@ fn breed_kitty_works() {
 	});
 }
 
+#[test]
+fn breed_kitty_sets_generation_and_cooldown() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		let kitty = KittiesModule::kitties(0).unwrap();
+		assert_eq!((kitty.generation, kitty.born_at, kitty.cooldown_until), (0, 1, 1));
+
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"));
+		reveal_births();
+		let child = KittiesModule::kitties(2).unwrap();
+		assert_eq!((child.generation, child.born_at, child.cooldown_until), (1, 1, 1));
+		// generation 0 parents rest for the base cooldown of the mock runtime
+		assert_eq!(KittiesModule::kitties(0).unwrap().cooldown_until, 11);
+		assert_eq!(KittiesModule::kitties(1).unwrap().cooldown_until, 11);
+
+		// the child of a generation 0 and a generation 1 kitty is generation 2
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 1));
+		System::set_block_number(11);
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 2, *b"testtest"));
+		reveal_births();
+		assert_eq!(KittiesModule::kitties(3).unwrap().generation, 2);
+		assert_eq!(KittiesModule::kitties(2).unwrap().cooldown_until, 11 + 20);
+	});
+}
+
+#[test]
+fn breed_kitty_cooling_down_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"));
+		reveal_births();
+
+		System::set_block_number(10);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 2, *b"testtest"),
+			Error::<Test>::KittyCoolingDown
+		);
+		System::set_block_number(11);
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 1));
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 2, *b"testtest"));
+		reveal_births();
+	});
+}
+
 #[test]
 fn breed_kitty_insufficient_balance_fails() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(1), 0, 0, 3));
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(1), 1, 0, 3));
 		// user 3 is in low balance
 		assert_noop!(
 			KittiesModule::breed(RuntimeOrigin::signed(3), 0, 1, *b"testtest"),
@@ -82,6 +246,7 @@ fn breed_kitty_insufficient_balance_fails() {
 fn breed_kitty_same_id_fails() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
 		assert_noop!(
 			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 0, *b"testtest"),
 			Error::<Test>::SameKittyId
@@ -93,6 +258,7 @@ fn breed_kitty_same_id_fails() {
 fn breed_kitty_invalid_id_fails() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
 		assert_noop!(
 			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
 			Error::<Test>::InvalidKittyId
@@ -100,10 +266,132 @@ fn breed_kitty_invalid_id_fails() {
 	});
 }
 
+#[test]
+fn breed_kitty_not_owner_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+		reveal_births();
+
+		// neither parent owned
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(3), 0, 1, *b"testtest"),
+			Error::<Test>::NotOwner
+		);
+		// one parent owned by someone else
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
+			Error::<Test>::NotOwner
+		);
+		// an offer for another account doesn't help
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(2), 1, 0, 3));
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn breed_kitty_with_siring_offer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::offer_siring(RuntimeOrigin::signed(1), 1, 100, 1),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::offer_siring(RuntimeOrigin::signed(2), 1, 100, 2),
+			Error::<Test>::AlreadyOwned
+		);
+
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(2), 1, 100, 1));
+		mock::System::assert_last_event(
+			Event::SiringOffered { owner: 2, kitty_id: 1, fee: 100, allowed: 1 }.into(),
+		);
+		let breeder_balance = Balances::free_balance(1);
+		let owner_balance = Balances::free_balance(2);
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"));
+		reveal_births();
+		assert_eq!(KittiesModule::kitty_owner(2), Some(1));
+		assert_eq!(KittiesModule::kitty_owner(1), Some(2));
+		mock::System::assert_has_event(
+			Event::SiringUsed { owner: 2, breeder: 1, kitty_id: 1, fee: 100 }.into(),
+		);
+
+		// the fee goes to the sire owner, on top of the kitty price
+		assert_eq!(Balances::free_balance(1), breeder_balance - 100 - KittiesModule::kitty_price());
+		assert_eq!(Balances::free_balance(2), owner_balance + 100);
+
+		// the offer is single use
+		assert_eq!(KittiesModule::siring_offer(1), None);
+		System::set_block_number(100);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn breed_kitty_with_siring_offer_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(2), 1, 100, 1));
+
+		// the sire is cooling down from breeding by its owner
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(2), 1, 2, *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
+			Error::<Test>::KittyCoolingDown
+		);
+
+		// the breeder can't pay the fee
+		System::set_block_number(100);
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(2), 1, 10_000_000, 1));
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn revoke_siring_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::revoke_siring(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::NoSiringOffer
+		);
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(2), 1, 0, 1));
+		assert_noop!(
+			KittiesModule::revoke_siring(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(KittiesModule::revoke_siring(RuntimeOrigin::signed(2), 1));
+		mock::System::assert_last_event(Event::SiringRevoked { owner: 2, kitty_id: 1 }.into());
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
 #[test]
 fn transfer_kitty_works() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
 		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 0));
 		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
 
@@ -117,6 +405,7 @@ fn transfer_kitty_works() {
 fn transfer_kitty_not_owner_fails() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
 		assert_noop!(
 			KittiesModule::transfer(RuntimeOrigin::signed(2), 2, 0),
 			Error::<Test>::NotOwner
@@ -135,71 +424,1753 @@ fn transfer_kitty_invalid_id_fails() {
 }
 
 #[test]
-fn sell_kitty_works() {
+fn transfer_kitty_clears_listing_and_siring_offer() {
 	new_test_ext().execute_with(|| {
-		// create a kitty
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
-		// sale the kitty
-		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0));
+		reveal_births();
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None));
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(1), 0, 0, 3));
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 0));
 
-		// the should be in on sale list
-		assert_eq!(KittiesModule::kitty_on_sale(0), Some(()));
+		assert_eq!(KittiesModule::kitty_on_sale(0), None);
+		assert_eq!(KittiesModule::siring_offer(0), None);
+		// the recipient never listed the kitty
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(3), 0, 100),
+			Error::<Test>::NotOnSale
+		);
+		// and the previous owner can no longer manage it
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None),
+			Error::<Test>::NotOwner
+		);
+	});
+}
 
-		// sale the kitty again
-		assert_noop!(KittiesModule::sale(RuntimeOrigin::signed(1), 0), Error::<Test>::AlreadyOnSale);
+#[test]
+fn approve_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::approve(RuntimeOrigin::signed(1), 0, Some(2)),
+			Error::<Test>::InvalidKittyId
+		);
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::approve(RuntimeOrigin::signed(2), 0, Some(2)),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::approve(RuntimeOrigin::signed(1), 0, Some(1)),
+			Error::<Test>::ApproveToSelf
+		);
 
-		mock::System::assert_last_event(Event::KittyOnSale { owner: 1, kitty_id: 0 }.into());
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(1), 0, Some(2)));
+		mock::System::assert_last_event(
+			Event::Approval { owner: 1, kitty_id: 0, operator: Some(2) }.into(),
+		);
+		assert_eq!(KittiesModule::approved(0), Some(2));
+		assert!(KittiesModule::is_approved(&2, 0));
+		assert!(!KittiesModule::is_approved(&3, 0));
+
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(1), 0, None));
+		assert_eq!(KittiesModule::approved(0), None);
+		assert!(!KittiesModule::is_approved(&2, 0));
 	});
 }
 
 #[test]
-fn sell_kitty_fails() {
+fn set_approval_for_all_works() {
 	new_test_ext().execute_with(|| {
-		// invalid kitty id
-		assert_noop!(KittiesModule::sale(RuntimeOrigin::signed(1), 0), Error::<Test>::InvalidKittyId);
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
-		// not the owner
-		assert_noop!(KittiesModule::sale(RuntimeOrigin::signed(2), 0), Error::<Test>::NotOwner);
+		reveal_births();
+		assert_noop!(
+			KittiesModule::set_approval_for_all(RuntimeOrigin::signed(1), 1, true),
+			Error::<Test>::ApproveToSelf
+		);
+
+		assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(1), 2, true));
+		mock::System::assert_last_event(
+			Event::ApprovalForAll { owner: 1, operator: 2, approved: true }.into(),
+		);
+		assert!(KittiesModule::is_approved_for_all(&1, &2));
+		assert!(KittiesModule::is_approved(&2, 0));
+
+		// an operator can approve single kitties
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(2), 0, Some(3)));
+		assert_eq!(KittiesModule::approved(0), Some(3));
+
+		assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(1), 2, false));
+		assert!(!KittiesModule::is_approved_for_all(&1, &2));
+		assert_noop!(
+			KittiesModule::approve(RuntimeOrigin::signed(2), 0, Some(3)),
+			Error::<Test>::NotOwner
+		);
 	});
 }
 
 #[test]
-fn buy_kitty_works() {
+fn transfer_from_works() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
-		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0));
-
-		// other user buy the kitty
-		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(2), 0));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(1), 0, Some(3)));
+		assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(1), 4, true));
 
-		// check new owner
+		assert_ok!(KittiesModule::transfer_from(RuntimeOrigin::signed(3), 1, 2, 0));
+		mock::System::assert_last_event(
+			Event::KittyTransferred { owner: 1, recipient: 2, kitty_id: 0 }.into(),
+		);
 		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
-		// the cat should not be in the on sale list
-		assert_eq!(KittiesModule::kitty_on_sale(0), None);
+		// the approval is cleared with the ownership change
+		assert_eq!(KittiesModule::approved(0), None);
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(3), 2, 1, 0),
+			Error::<Test>::NotApproved
+		);
 
-		mock::System::assert_last_event(Event::BuyKitty { buyer: 2, owner: 1, kitty_id: 0 }.into());
+		// operators keep their approval for the remaining kitties
+		assert_ok!(KittiesModule::transfer_from(RuntimeOrigin::signed(4), 1, 4, 1));
+		assert_eq!(KittiesModule::kitty_owner(1), Some(4));
+		assert!(KittiesModule::is_approved_for_all(&1, &4));
 	});
 }
 
 #[test]
-fn buy_kitty_fails() {
+fn transfer_from_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(1), 0, Some(3)));
+
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(3), 1, 2, 1),
+			Error::<Test>::InvalidKittyId
+		);
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(3), 2, 3, 0),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(2), 1, 2, 0),
+			Error::<Test>::NotApproved
+		);
+
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(3), 1, 2, 0),
+			Error::<Test>::KittyLocked
+		);
+	});
+}
+
+#[test]
+fn release_kitty_works() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
-		assert_noop!(KittiesModule::buy(RuntimeOrigin::signed(1), 0), Error::<Test>::AlreadyOwned);
-		assert_noop!(KittiesModule::buy(RuntimeOrigin::signed(2), 0), Error::<Test>::NotOnSale);
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None));
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(1), 0, 0, 2));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 100, 10));
+
+		assert_eq!(KittiesModule::kitty_deposit(2), DefaultKittyPrice::get());
+
+		let owner_balance = Balances::free_balance(1);
+		assert_ok!(KittiesModule::release(RuntimeOrigin::signed(1), 0));
+		mock::System::assert_last_event(
+			Event::KittyReleased { owner: 1, kitty_id: 0, refund: 5 }.into(),
+		);
+
+		// half of the kitty price is refunded in the mock runtime
+		assert_eq!(Balances::free_balance(1), owner_balance + 5);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(KittiesModule::kitties(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), None);
+		assert_eq!(KittiesModule::kitty_on_sale(0), None);
+		assert_eq!(KittiesModule::siring_offer(0), None);
+		assert_eq!(KittiesModule::kitty_deposit(0), 0);
+		assert!(KittiesModule::offers(0).is_empty());
+		assert_eq!(KittiesModule::owned_kitties(1).into_inner(), vec![1, 2]);
+		// the lineage of the offspring is kept
+		assert_eq!(KittiesModule::kitty_parents(2), Some((0, 1)));
 	});
 }
 
 #[test]
-fn buy_kitty_insufficient_balance_fails() {
+fn release_kitty_fails() {
 	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::release(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::InvalidKittyId
+		);
 		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
-		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0));
+		// not born yet
+		assert_noop!(
+			KittiesModule::release(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::InvalidKittyId
+		);
+		reveal_births();
+		assert_noop!(KittiesModule::release(RuntimeOrigin::signed(2), 0), Error::<Test>::NotOwner);
 
-		// user 3 is in low balance
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
 		assert_noop!(
-			KittiesModule::buy(RuntimeOrigin::signed(3), 0),
-			pallet_balances::Error::<Test>::InsufficientBalance
+			KittiesModule::release(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::KittyLocked
+		);
+	});
+}
+
+#[test]
+fn sell_kitty_works() {
+	new_test_ext().execute_with(|| {
+		// create a kitty
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		// sale the kitty
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, Some(10)));
+
+		// the should be in on sale list
+		assert_eq!(
+			KittiesModule::kitty_on_sale(0),
+			Some(Listing { price: 100, expires_at: Some(10) })
+		);
+
+		// sale the kitty again
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None),
+			Error::<Test>::AlreadyOnSale
+		);
+
+		mock::System::assert_last_event(
+			Event::KittyOnSale { owner: 1, kitty_id: 0, price: 100, expires_at: Some(10) }.into(),
+		);
+
+		// an expired listing can be replaced
+		System::set_block_number(10);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 200, None));
+		assert_eq!(KittiesModule::kitty_on_sale(0), Some(Listing { price: 200, expires_at: None }));
+	});
+}
+
+#[test]
+fn sell_kitty_fails() {
+	new_test_ext().execute_with(|| {
+		// invalid kitty id
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None),
+			Error::<Test>::InvalidKittyId
+		);
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		// not the owner
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(2), 0, 100, None),
+			Error::<Test>::NotOwner
+		);
+		// expiry in the past
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, Some(1)),
+			Error::<Test>::InvalidExpiry
+		);
+	});
+}
+
+#[test]
+fn cancel_sale_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::cancel_sale(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NotOnSale
+		);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None));
+		assert_noop!(
+			KittiesModule::cancel_sale(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(KittiesModule::cancel_sale(RuntimeOrigin::signed(1), 0));
+		assert_eq!(KittiesModule::kitty_on_sale(0), None);
+		mock::System::assert_last_event(Event::KittySaleCancelled { owner: 1, kitty_id: 0 }.into());
+
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(2), 0, 100),
+			Error::<Test>::NotOnSale
+		);
+	});
+}
+
+#[test]
+fn update_price_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::update_price(RuntimeOrigin::signed(1), 0, 200),
+			Error::<Test>::NotOnSale
+		);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, Some(10)));
+		assert_noop!(
+			KittiesModule::update_price(RuntimeOrigin::signed(2), 0, 200),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(KittiesModule::update_price(RuntimeOrigin::signed(1), 0, 200));
+		assert_eq!(
+			KittiesModule::kitty_on_sale(0),
+			Some(Listing { price: 200, expires_at: Some(10) })
+		);
+		mock::System::assert_last_event(
+			Event::KittyPriceUpdated { owner: 1, kitty_id: 0, price: 200 }.into(),
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			KittiesModule::update_price(RuntimeOrigin::signed(1), 0, 300),
+			Error::<Test>::ListingExpired
+		);
+	});
+}
+
+#[test]
+fn buy_kitty_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None));
+
+		// other user buy the kitty
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(2), 0, 150));
+
+		// check new owner
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		// the cat should not be in the on sale list
+		assert_eq!(KittiesModule::kitty_on_sale(0), None);
+		// the seller's price is paid, not the buyer's maximum
+		assert_eq!(Balances::free_balance(2), 10_000_000 - 100);
+
+		mock::System::assert_last_event(
+			Event::BuyKitty { buyer: 2, owner: 1, kitty_id: 0, price: 100 }.into(),
+		);
+	});
+}
+
+#[test]
+fn buy_kitty_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(1), 0, 100),
+			Error::<Test>::AlreadyOwned
+		);
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(2), 0, 100),
+			Error::<Test>::NotOnSale
+		);
+
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, Some(10)));
+		// the price was raised above what the buyer accepts
+		assert_ok!(KittiesModule::update_price(RuntimeOrigin::signed(1), 0, 200));
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(2), 0, 100),
+			Error::<Test>::PriceTooHigh
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(2), 0, 200),
+			Error::<Test>::ListingExpired
+		);
+	});
+}
+
+#[test]
+fn buy_kitty_insufficient_balance_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None));
+
+		// user 3 is in low balance
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(3), 0, 100),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn set_kitty_price_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(KittiesModule::kitty_price(), DefaultKittyPrice::get());
+		assert_noop!(
+			KittiesModule::set_kitty_price(RuntimeOrigin::signed(1), 20),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(KittiesModule::set_kitty_price(RuntimeOrigin::root(), 20));
+		mock::System::assert_last_event(Event::KittyPriceSet { price: 20 }.into());
+		assert_eq!(KittiesModule::kitty_price(), 20);
+
+		let balance = Balances::free_balance(1);
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_eq!(Balances::free_balance(1), balance - 20);
+		assert_ok!(KittiesModule::release(RuntimeOrigin::signed(1), 0));
+		assert_eq!(Balances::free_balance(1), balance - 10);
+	});
+}
+
+#[test]
+fn release_refunds_the_price_paid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+
+		// raising the price doesn't raise the refund of kitties minted before
+		assert_ok!(KittiesModule::set_kitty_price(RuntimeOrigin::root(), 1_000));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+		reveal_births();
+		let balance = Balances::free_balance(1);
+		assert_ok!(KittiesModule::release(RuntimeOrigin::signed(1), 0));
+		mock::System::assert_last_event(
+			Event::KittyReleased { owner: 1, kitty_id: 0, refund: 5 }.into(),
+		);
+		assert_eq!(Balances::free_balance(1), balance + 5);
+
+		// nor does lowering it cut the refund of kitties minted at the higher price
+		assert_ok!(KittiesModule::set_kitty_price(RuntimeOrigin::root(), 2));
+		let balance = Balances::free_balance(2);
+		assert_ok!(KittiesModule::release(RuntimeOrigin::signed(2), 1));
+		assert_eq!(Balances::free_balance(2), balance + 500);
+	});
+}
+
+#[test]
+fn withdraw_treasury_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_noop!(
+			KittiesModule::withdraw_treasury(RuntimeOrigin::signed(1), 5, 3),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(KittiesModule::withdraw_treasury(RuntimeOrigin::root(), 5, 3));
+		mock::System::assert_last_event(
+			Event::TreasuryWithdrawn { amount: 5, beneficiary: 3 }.into(),
+		);
+		assert_eq!(Balances::free_balance(3), 10);
+
+		// the pallet account is kept alive
+		assert_noop!(
+			KittiesModule::withdraw_treasury(RuntimeOrigin::root(), 5, 3),
+			pallet_balances::Error::<Test>::KeepAlive
+		);
+	});
+}
+
+#[test]
+fn nonfungibles_inspect_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&(), &0), None);
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&(), &0));
+		assert_eq!(KittiesModule::owned(&1).count(), 0);
+
+		reveal_births();
+		let kitty = KittiesModule::kitties(0).unwrap();
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&(), &0), Some(1));
+		assert_eq!(KittiesModule::attribute(&(), &0, b"dna"), Some(kitty.dna.to_vec()));
+		assert_eq!(KittiesModule::attribute(&(), &0, b"name"), Some(b"testtest".to_vec()));
+		assert_eq!(KittiesModule::attribute(&(), &0, b"color"), None);
+		assert!(<KittiesModule as Inspect<u64>>::can_transfer(&(), &0));
+
+		assert_eq!(KittiesModule::collections().collect::<Vec<_>>(), vec![()]);
+		assert_eq!(KittiesModule::items(&()).collect::<Vec<_>>(), vec![0]);
+		assert_eq!(KittiesModule::owned(&1).collect::<Vec<_>>(), vec![((), 0)]);
+		assert_eq!(KittiesModule::owned_in_collection(&(), &2).count(), 0);
+
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&(), &0));
+	});
+}
+
+#[test]
+fn nonfungibles_mutate_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::mint_into(&(), &1, &1),
+			Error::<Test>::InvalidKittyId
+		);
+		let balance = Balances::free_balance(1);
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&(), &0, &1));
+		// born right away, without waiting for a reveal block
+		let kitty = KittiesModule::kitties(0).unwrap();
+		mock::System::assert_last_event(
+			Event::KittyCreated { owner: 1, kitty_id: 0, kitty }.into(),
+		);
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&(), &0), Some(1));
+		assert_eq!(KittiesModule::owned_kitties(1).into_inner(), vec![0]);
+		assert_eq!(KittiesModule::pending_birth(0), None);
+		assert_eq!(Balances::free_balance(1), balance);
+
+		assert_ok!(<KittiesModule as Transfer<u64>>::transfer(&(), &0, &2));
+		mock::System::assert_last_event(
+			Event::KittyTransferred { owner: 1, recipient: 2, kitty_id: 0 }.into(),
+		);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::burn(&(), &0, Some(&1)),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(<KittiesModule as Mutate<u64>>::burn(&(), &0, Some(&2)));
+		mock::System::assert_last_event(Event::KittyBurned { owner: 2, kitty_id: 0 }.into());
+		assert_eq!(KittiesModule::kitties(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), None);
+		assert!(KittiesModule::owned_kitties(2).is_empty());
+		assert_noop!(
+			<KittiesModule as Transfer<u64>>::transfer(&(), &0, &1),
+			Error::<Test>::InvalidKittyId
+		);
+	});
+}
+
+#[test]
+fn migrate_listings_to_v3_works() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<KittiesModule>();
+		// a v2 listing is an empty value under the kitty id
+		let key = KittyOnSale::<Test>::hashed_key_for(0);
+		frame_support::storage::unhashed::put_raw(&key, &().encode());
+
+		KittiesModule::on_runtime_upgrade();
+
+		assert_eq!(
+			KittiesModule::kitty_on_sale(0),
+			Some(Listing { price: DefaultKittyPrice::get(), expires_at: None })
+		);
+		assert_eq!(KittiesModule::on_chain_storage_version(), 3);
+	});
+}
+
+#[test]
+fn buy_kitty_clears_siring_offer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(1), 0, 0, 3));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(2), 0, 100));
+
+		assert_eq!(KittiesModule::siring_offer(0), None);
+		assert_noop!(
+			KittiesModule::cancel_sale(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn english_auction_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
+		mock::System::assert_last_event(
+			Event::AuctionCreated {
+				seller: 1,
+				kitty_id: 0,
+				kind: AuctionKind::English,
+				reserve_price: 100,
+				end: 11,
+			}
+			.into(),
+		);
+		assert_eq!(KittiesModule::auctions_ending_at(11).into_inner(), vec![0]);
+
+		// below the reserve price
+		assert_noop!(KittiesModule::bid(RuntimeOrigin::signed(2), 0, 50), Error::<Test>::BidTooLow);
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(2), 0, 100));
+		assert_eq!(Balances::reserved_balance(2), 100);
+
+		// outbid, the previous bid is refunded
+		Balances::make_free_balance_be(&4, 1_000);
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(4), 0, 100),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(4), 0, 150));
+		mock::System::assert_last_event(
+			Event::BidPlaced { bidder: 4, kitty_id: 0, amount: 150 }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::reserved_balance(4), 150);
+
+		let seller_balance = Balances::free_balance(1);
+		System::set_block_number(11);
+		KittiesModule::on_initialize(11);
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(4));
+		assert_eq!(KittiesModule::auctions(0), None);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(4), 850);
+		assert_eq!(Balances::free_balance(1), seller_balance + 150);
+		mock::System::assert_last_event(
+			Event::AuctionWon { seller: 1, buyer: 4, kitty_id: 0, price: 150 }.into(),
+		);
+	});
+}
+
+#[test]
+fn english_auction_fails_when_bid_is_slashed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
+		Balances::make_free_balance_be(&4, 1_000);
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(4), 0, 150));
+
+		// the reserve of the bid shrinks before the auction ends
+		let _ = Balances::slash_reserved(&4, 50);
+		assert_eq!(Balances::reserved_balance(4), 100);
+
+		let seller_balance = Balances::free_balance(1);
+		System::set_block_number(11);
+		KittiesModule::on_initialize(11);
+
+		// nothing is paid and the kitty stays with the seller
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(KittiesModule::auctions(0), None);
+		assert_eq!(Balances::free_balance(1), seller_balance);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(4), 950);
+		mock::System::assert_last_event(
+			Event::AuctionFailed { seller: 1, buyer: 4, kitty_id: 0 }.into(),
+		);
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 0));
+	});
+}
+
+#[test]
+fn english_auction_without_bids_expires() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
+
+		System::set_block_number(11);
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(2), 0, 100),
+			Error::<Test>::AuctionEnded
+		);
+		KittiesModule::on_initialize(11);
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(KittiesModule::auctions(0), None);
+		mock::System::assert_last_event(Event::AuctionExpired { seller: 1, kitty_id: 0 }.into());
+		// the kitty is unlocked again
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 0));
+	});
+}
+
+#[test]
+fn dutch_auction_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::Dutch { start_price: 1_000 },
+			100,
+			10
+		));
+		let auction = KittiesModule::auctions(0).unwrap();
+		assert_eq!(KittiesModule::dutch_price(&auction), 1_000);
+
+		// half way through, half of the way down to the reserve price
+		System::set_block_number(6);
+		assert_eq!(KittiesModule::dutch_price(&auction), 550);
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(2), 0, 500),
+			Error::<Test>::BidTooLow
+		);
+
+		// the current price is paid, not the bid
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(2), 0, 600));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::free_balance(2), 10_000_000 - 550);
+		assert_eq!(KittiesModule::auctions(0), None);
+		assert!(KittiesModule::auctions_ending_at(11).is_empty());
+		mock::System::assert_last_event(
+			Event::AuctionWon { seller: 1, buyer: 2, kitty_id: 0, price: 550 }.into(),
+		);
+	});
+}
+
+#[test]
+fn create_auction_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::create_auction(
+				RuntimeOrigin::signed(2),
+				0,
+				AuctionKind::English,
+				100,
+				10
+			),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::create_auction(
+				RuntimeOrigin::signed(1),
+				0,
+				AuctionKind::English,
+				100,
+				0
+			),
+			Error::<Test>::InvalidAuctionDuration
+		);
+		assert_noop!(
+			KittiesModule::create_auction(
+				RuntimeOrigin::signed(1),
+				0,
+				AuctionKind::English,
+				100,
+				101
+			),
+			Error::<Test>::InvalidAuctionDuration
+		);
+		assert_noop!(
+			KittiesModule::create_auction(
+				RuntimeOrigin::signed(1),
+				0,
+				AuctionKind::Dutch { start_price: 100 },
+				100,
+				10
+			),
+			Error::<Test>::InvalidStartPrice
+		);
+
+		// at most two auctions end in the same block in the mock runtime
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		for kitty_id in 0..2 {
+			assert_ok!(KittiesModule::create_auction(
+				RuntimeOrigin::signed(1),
+				kitty_id,
+				AuctionKind::English,
+				100,
+				10
+			));
+		}
+		assert_noop!(
+			KittiesModule::create_auction(
+				RuntimeOrigin::signed(1),
+				2,
+				AuctionKind::English,
+				100,
+				10
+			),
+			Error::<Test>::TooManyAuctions
+		);
+		assert_noop!(
+			KittiesModule::create_auction(
+				RuntimeOrigin::signed(1),
+				0,
+				AuctionKind::English,
+				100,
+				5
+			),
+			Error::<Test>::KittyLocked
+		);
+	});
+}
+
+#[test]
+fn kitty_under_auction_is_locked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None));
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
+
+		// the listing was cancelled
+		assert_eq!(KittiesModule::kitty_on_sale(0), None);
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 0),
+			Error::<Test>::KittyLocked
+		);
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None),
+			Error::<Test>::KittyLocked
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
+			Error::<Test>::KittyLocked
+		);
+	});
+}
+
+#[test]
+fn cancel_auction_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::cancel_auction(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NotInAuction
+		);
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(1), 0, 100),
+			Error::<Test>::AlreadyOwned
+		);
+		assert_noop!(
+			KittiesModule::cancel_auction(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(KittiesModule::cancel_auction(RuntimeOrigin::signed(1), 0));
+		assert_eq!(KittiesModule::auctions(0), None);
+		assert!(KittiesModule::auctions_ending_at(11).is_empty());
+		mock::System::assert_last_event(Event::AuctionCancelled { seller: 1, kitty_id: 0 }.into());
+
+		// bids can't be withdrawn by cancelling
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			10
+		));
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(2), 0, 100));
+		assert_noop!(
+			KittiesModule::cancel_auction(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::AuctionHasBids
+		);
+	});
+}
+
+#[test]
+fn make_offer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 100, 10));
+
+		assert_eq!(
+			KittiesModule::offers(0).into_inner(),
+			vec![Offer { buyer: 2, amount: 100, expires_at: 10 }]
+		);
+		assert_eq!(Balances::reserved_balance(2), 100);
+		mock::System::assert_last_event(
+			Event::OfferMade { buyer: 2, kitty_id: 0, amount: 100, expires_at: 10 }.into(),
+		);
+	});
+}
+
+#[test]
+fn make_offer_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 100, 10),
+			Error::<Test>::InvalidKittyId
+		);
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(1), 0, 100, 10),
+			Error::<Test>::AlreadyOwned
+		);
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 100, 1),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(3), 0, 100, 10),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 100, 10));
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 200, 10),
+			Error::<Test>::OfferAlreadyExists
+		);
+
+		// at most two offers per kitty in the mock runtime
+		Balances::make_free_balance_be(&4, 1_000);
+		Balances::make_free_balance_be(&5, 1_000);
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(4), 0, 100, 5));
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(5), 0, 100, 10),
+			Error::<Test>::TooManyOffers
+		);
+
+		// expired offers are pruned to make room
+		System::set_block_number(5);
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(5), 0, 100, 10));
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(KittiesModule::offers(0).len(), 2);
+	});
+}
+
+#[test]
+fn withdraw_offer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::withdraw_offer(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NoOffer
+		);
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 100, 10));
+
+		// expired offers can still be withdrawn
+		System::set_block_number(10);
+		assert_ok!(KittiesModule::withdraw_offer(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(KittiesModule::offers(0).is_empty());
+		mock::System::assert_last_event(Event::OfferWithdrawn { buyer: 2, kitty_id: 0 }.into());
+	});
+}
+
+#[test]
+fn accept_offer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 500, None));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 100, 10));
+		let seller_balance = Balances::free_balance(1);
+
+		assert_noop!(
+			KittiesModule::accept_offer(RuntimeOrigin::signed(2), 0, 2),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::accept_offer(RuntimeOrigin::signed(1), 0, 3),
+			Error::<Test>::NoOffer
+		);
+		assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(1), 0, 2));
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::free_balance(1), seller_balance + 100);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(KittiesModule::offers(0).is_empty());
+		// ownership changed like a transfer, so the listing is gone
+		assert_eq!(KittiesModule::kitty_on_sale(0), None);
+		mock::System::assert_last_event(
+			Event::OfferAccepted { owner: 1, buyer: 2, kitty_id: 0, amount: 100 }.into(),
+		);
+	});
+}
+
+#[test]
+fn accept_offer_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(2), 0, 100, 10));
+		assert_ok!(KittiesModule::create_auction(
+			RuntimeOrigin::signed(1),
+			0,
+			AuctionKind::English,
+			100,
+			5
+		));
+		assert_noop!(
+			KittiesModule::accept_offer(RuntimeOrigin::signed(1), 0, 2),
+			Error::<Test>::KittyLocked
+		);
+		assert_ok!(KittiesModule::cancel_auction(RuntimeOrigin::signed(1), 0));
+
+		System::set_block_number(10);
+		assert_noop!(
+			KittiesModule::accept_offer(RuntimeOrigin::signed(1), 0, 2),
+			Error::<Test>::OfferExpired
+		);
+	});
+}
+
+#[test]
+fn owned_kitties_index_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"));
+		reveal_births();
+		assert_eq!(KittiesModule::owned_kitties(1).into_inner(), vec![0, 1, 2]);
+
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, 1));
+		assert_eq!(KittiesModule::owned_kitties(1).into_inner(), vec![0, 2]);
+		assert_eq!(KittiesModule::owned_kitties(2).into_inner(), vec![1]);
+
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 0, 100, None));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(2), 0, 100));
+		assert_eq!(KittiesModule::owned_kitties(1).into_inner(), vec![2]);
+		assert_eq!(KittiesModule::owned_kitties(2).into_inner(), vec![1, 0]);
+	});
+}
+
+#[test]
+fn too_many_kitties_fails() {
+	new_test_ext().execute_with(|| {
+		// at most three kitties per account in the mock runtime
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+			reveal_births();
+		}
+		assert_noop!(
+			KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"),
+			Error::<Test>::TooManyKitties
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(1), 0, 1, *b"testtest"),
+			Error::<Test>::TooManyKitties
+		);
+
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(2), *b"testtest"));
+		reveal_births();
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(2), 1, 3),
+			Error::<Test>::TooManyKitties
+		);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(2), 3, 100, None));
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(1), 3, 100),
+			Error::<Test>::TooManyKitties
+		);
+	});
+}
+
+#[test]
+fn migrate_owner_index_to_v3_works() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<KittiesModule>();
+		KittyOwner::<Test>::insert(0, 1);
+		KittyOwner::<Test>::insert(1, 2);
+		KittyOwner::<Test>::insert(2, 1);
+
+		KittiesModule::on_runtime_upgrade();
+
+		let mut owned = KittiesModule::owned_kitties(1).into_inner();
+		owned.sort();
+		assert_eq!(owned, vec![0, 2]);
+		assert_eq!(KittiesModule::owned_kitties(2).into_inner(), vec![1]);
+	});
+}
+
+#[test]
+fn migrate_kitties_to_v3_works() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(2).put::<KittiesModule>();
+		for index in 0..4u32 {
+			let kitty = migrations::v2::V2Kitty { dna: [index as u8; 16], name: *b"testtest" };
+			frame_support::storage::unhashed::put(&Kitties::<Test>::hashed_key_for(index), &kitty);
+		}
+		KittyParents::<Test>::insert(2, (0, 1));
+		KittyParents::<Test>::insert(3, (2, 0));
+		KittyPrice::<Test>::put(30);
+
+		KittiesModule::on_runtime_upgrade();
+
+		let generations: Vec<u32> =
+			(0..4).map(|index| KittiesModule::kitties(index).unwrap().generation).collect();
+		assert_eq!(generations, vec![0, 0, 1, 2]);
+		assert_eq!(
+			KittiesModule::kitties(3),
+			Some(Kitty {
+				dna: [3; 16],
+				name: *b"testtest",
+				generation: 2,
+				born_at: 0,
+				cooldown_until: 0,
+			})
+		);
+		// the kitties were paid at the price before the upgrade
+		for index in 0..4 {
+			assert_eq!(KittiesModule::kitty_deposit(index), 30);
+		}
+	});
+}
+
+fn dna(alleles: [(u8, u8); 8]) -> Dna {
+	let mut dna = [0u8; 16];
+	for (index, (dominant, recessive)) in alleles.iter().enumerate() {
+		dna[index * 2] = *dominant;
+		dna[index * 2 + 1] = *recessive;
+	}
+	dna
+}
+
+#[test]
+fn decode_works() {
+	let traits = decode(&dna([(9, 2), (13, 1), (3, 35), (0, 0), (0, 0), (0, 0), (0, 0), (0, 0)]));
+
+	assert_eq!(traits.len(), 8);
+	assert_eq!(traits[0], Trait { gene: Gene::Body, dominant: 1, recessive: 2 });
+	assert_eq!(traits[1], Trait { gene: Gene::Pattern, dominant: 1, recessive: 1 });
+	assert_eq!(traits[2], Trait { gene: Gene::BodyColor, dominant: 3, recessive: 3 });
+}
+
+#[test]
+fn inherit_is_deterministic() {
+	let dna_1 = dna([(1, 2); 8]);
+	let dna_2 = dna([(3, 4); 8]);
+
+	assert_eq!(
+		inherit(&dna_1, &dna_2, b"seed", Permill::zero()),
+		inherit(&dna_1, &dna_2, b"seed", Permill::zero())
+	);
+}
+
+#[test]
+fn inherit_takes_one_allele_from_each_parent() {
+	let dna_1 = dna([(1, 2); 8]);
+	let dna_2 = dna([(3, 4); 8]);
+
+	for seed in 0u8..32 {
+		let child = inherit(&dna_1, &dna_2, &[seed], Permill::zero());
+		for gene in Gene::ALL {
+			let (dominant, recessive) = (child[gene.offset()], child[gene.offset() + 1]);
+			// the lower variant of the two is dominant
+			assert!([1, 2].contains(&dominant));
+			assert!([3, 4].contains(&recessive));
+		}
+	}
+}
+
+#[test]
+fn inherit_mutates_at_mutation_rate() {
+	let dna_1 = dna([(1, 1); 8]);
+
+	for seed in 0u8..32 {
+		// without mutations only the parents' alleles are passed on
+		let child = inherit(&dna_1, &dna_1, &[seed], Permill::zero());
+		assert_eq!(child, dna_1);
+
+		// mutations replace the recessive allele only
+		let child = inherit(&dna_1, &dna_1, &[seed], Permill::one());
+		for gene in Gene::ALL {
+			assert_eq!(child[gene.offset()], 1);
+		}
+	}
+
+	let mutated = (0u8..32)
+		.filter(|seed| inherit(&dna_1, &dna_1, &[*seed], Permill::one()) != dna_1)
+		.count();
+	assert!(mutated > 0);
+}
+
+#[test]
+fn kitty_traits_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(KittiesModule::traits(0), None);
+		assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		reveal_births();
+
+		let dna = KittiesModule::kitties(0).unwrap().dna;
+		assert_eq!(KittiesModule::traits(0), Some(decode(&dna)));
+	});
+}
+
+#[test]
+fn kitty_queries_work() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create_kitty(RuntimeOrigin::signed(1), *b"testtest"));
+		}
+		assert_eq!(KittiesModule::kitty_info(0), None);
+		assert!(KittiesModule::kitties_of(&1, 0, 10).is_empty());
+
+		reveal_births();
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 1, 100, Some(10)));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(1), 2, 200, None));
+		assert_eq!(
+			KittiesModule::kitty_info(1),
+			Some(KittyInfo {
+				kitty_id: 1,
+				kitty: KittiesModule::kitties(1).unwrap(),
+				owner: 1,
+				listing: Some(Listing { price: 100, expires_at: Some(10) }),
+			})
+		);
+		assert_eq!(KittiesModule::kitty_info(0).unwrap().listing, None);
+
+		let page = KittiesModule::kitties_of(&1, 1, 1);
+		assert_eq!(page.iter().map(|info| info.kitty_id).collect::<Vec<_>>(), vec![1]);
+		assert_eq!(KittiesModule::kitties_of(&1, 0, 10).len(), 3);
+		assert!(KittiesModule::kitties_of(&2, 0, 10).is_empty());
+
+		let mut for_sale: Vec<_> =
+			KittiesModule::kitties_for_sale().iter().map(|info| info.kitty_id).collect();
+		for_sale.sort();
+		assert_eq!(for_sale, vec![1, 2]);
+
+		// expired listings are not for sale
+		System::set_block_number(10);
+		assert_eq!(KittiesModule::kitty_info(1).unwrap().listing, None);
+		let for_sale: Vec<_> =
+			KittiesModule::kitties_for_sale().iter().map(|info| info.kitty_id).collect();
+		assert_eq!(for_sale, vec![2]);
+	});
+}
+
+#[test]
+fn family_tree_queries_work() {
+	new_test_ext().execute_with(|| {
+		KittyParents::<Test>::insert(2, (0, 1));
+		KittyParents::<Test>::insert(3, (2, 1));
+		KittyParents::<Test>::insert(4, (3, 0));
+		KittyParents::<Test>::insert(5, (1, 0));
+
+		assert!(KittiesModule::ancestors(0, 10).is_empty());
+		assert!(KittiesModule::ancestors(4, 0).is_empty());
+		assert_eq!(KittiesModule::ancestors(4, 1), vec![3, 0]);
+		assert_eq!(KittiesModule::ancestors(4, 10), vec![3, 0, 2, 1]);
+
+		assert_eq!(KittiesModule::descendants(0), vec![2, 3, 4, 5]);
+		assert_eq!(KittiesModule::descendants(2), vec![3, 4]);
+		assert!(KittiesModule::descendants(4).is_empty());
+	});
+}
+
+fn price_payload(reporter: u64, price: &[u8]) -> Payload<UintAuthorityId> {
+	Payload {
+		symbol: Symbol::try_from(b"BTCUSDT".to_vec()).unwrap(),
+		price: parse_price(price).unwrap(),
+		public: UintAuthorityId(reporter),
+	}
+}
+
+fn submit_price(reporter: u64, price: &[u8]) -> DispatchResult {
+	KittiesModule::unsigned_extrinsic_with_signed_payload(
+		RuntimeOrigin::none(),
+		price_payload(reporter, price),
+		TestSignature(reporter, vec![]),
+	)
+}
+
+fn btc_usdt() -> Symbol {
+	Symbol::try_from(b"BTCUSDT".to_vec()).unwrap()
+}
+
+#[test]
+fn submit_price_works() {
+	new_test_ext().execute_with(|| {
+		Timestamp::set_timestamp(1_000);
+		let symbol = btc_usdt();
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 9));
+		assert_eq!(KittiesModule::latest_price(b"BTCUSDT"), None);
+
+		assert_ok!(submit_price(7, b"27000.50"));
+		let price = FixedU128::saturating_from_rational(270_005, 10);
+		mock::System::assert_last_event(
+			Event::PriceSubmitted { symbol: symbol.clone(), round: 0, reporter: 7, price }.into(),
+		);
+		assert_eq!(KittiesModule::round(&symbol), Round { id: 0, started_at: 1 });
+		assert_eq!(KittiesModule::round_submissions(&symbol).to_vec(), vec![(7, price)]);
+		assert!(KittiesModule::has_submitted(&symbol, &7));
+		assert_noop!(submit_price(7, b"27001"), Error::<Test>::AlreadySubmitted);
+
+		// the third submission reaches the quorum
+		System::set_block_number(2);
+		assert_ok!(submit_price(8, b"27100"));
+		assert_eq!(KittiesModule::price(&symbol), None);
+		assert_ok!(submit_price(9, b"26900"));
+		mock::System::assert_last_event(
+			Event::RoundFinalized { symbol: symbol.clone(), round: 0, price, outliers: vec![] }
+				.into(),
+		);
+		assert_eq!(
+			KittiesModule::price(&symbol),
+			Some(PriceRecord { price, round: 0, reporters: 3, block: 2, timestamp: 1_000 })
+		);
+		assert_eq!(KittiesModule::latest_price(b"BTCUSDT"), Some((price, 2)));
+		assert_eq!(KittiesModule::round(&symbol).id, 1);
+		assert!(KittiesModule::round_submissions(&symbol).is_empty());
+		assert!(!KittiesModule::has_submitted(&symbol, &7));
+		assert_eq!(KittiesModule::latest_price(b"ETHUSDT"), None);
+	});
+}
+
+#[test]
+fn price_round_reports_outliers() {
+	new_test_ext().execute_with(|| {
+		let symbol = btc_usdt();
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 9));
+
+		assert_ok!(submit_price(7, b"100"));
+		assert_ok!(submit_price(8, b"120"));
+		assert_ok!(submit_price(9, b"101"));
+		let price = FixedU128::from_u32(101);
+		mock::System::assert_last_event(
+			Event::RoundFinalized { symbol: symbol.clone(), round: 0, price, outliers: vec![8] }
+				.into(),
+		);
+		assert_eq!(KittiesModule::price(&symbol).unwrap().price, price);
+	});
+}
+
+#[test]
+fn price_round_expires() {
+	new_test_ext().execute_with(|| {
+		let symbol = btc_usdt();
+		assert_ok!(submit_price(7, b"100"));
+
+		// the round is open until block 6
+		System::set_block_number(6);
+		assert!(!KittiesModule::has_submitted(&symbol, &7));
+		assert_ok!(submit_price(8, b"101"));
+		let expired = RuntimeEvent::from(Event::RoundExpired { symbol: symbol.clone(), round: 0 });
+		assert!(System::events().iter().any(|record| record.event == expired));
+		assert_eq!(KittiesModule::round(&symbol), Round { id: 1, started_at: 6 });
+		assert_eq!(
+			KittiesModule::round_submissions(&symbol).to_vec(),
+			vec![(8, FixedU128::from_u32(101))]
+		);
+		assert_ok!(submit_price(7, b"100"));
+		assert_eq!(KittiesModule::price(&symbol), None);
+	});
+}
+
+#[test]
+fn price_goes_stale() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 9));
+		for reporter in [7, 8, 9] {
+			assert_ok!(submit_price(reporter, b"100"));
+		}
+		let price = FixedU128::from_u32(100);
+
+		System::set_block_number(10);
+		assert_eq!(KittiesModule::latest_price(b"BTCUSDT"), Some((price, 1)));
+		System::set_block_number(11);
+		assert!(KittiesModule::is_stale(&KittiesModule::price(&btc_usdt()).unwrap()));
+		assert_eq!(KittiesModule::latest_price(b"BTCUSDT"), None);
+	});
+}
+
+#[test]
+fn median_works() {
+	let prices =
+		|prices: &[u32]| prices.iter().map(|p| FixedU128::from_u32(*p)).collect::<Vec<_>>();
+	assert_eq!(KittiesModule::median(&prices(&[5])), FixedU128::from_u32(5));
+	assert_eq!(KittiesModule::median(&prices(&[1, 2, 4])), FixedU128::from_u32(2));
+	assert_eq!(KittiesModule::median(&prices(&[1, 2])), FixedU128::saturating_from_rational(3, 2));
+	assert_eq!(
+		KittiesModule::median(&[FixedU128::from_inner(1), FixedU128::from_inner(3)]),
+		FixedU128::from_inner(2)
+	);
+}
+
+#[test]
+fn submit_price_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::unsigned_extrinsic_with_signed_payload(
+				RuntimeOrigin::signed(1),
+				price_payload(7, b"27000.50"),
+				TestSignature(7, vec![]),
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			KittiesModule::unsigned_extrinsic_with_signed_payload(
+				RuntimeOrigin::none(),
+				price_payload(7, b"0.0"),
+				TestSignature(7, vec![]),
+			),
+			Error::<Test>::InvalidPrice
+		);
+		assert_noop!(
+			KittiesModule::unsigned_extrinsic_with_signed_payload(
+				RuntimeOrigin::none(),
+				price_payload(9, b"27000.50"),
+				TestSignature(9, vec![]),
+			),
+			Error::<Test>::NotOracleAuthority
+		);
+	});
+}
+
+#[test]
+fn oracle_authorities_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(KittiesModule::oracle_authorities().to_vec(), vec![7, 8]);
+		assert_noop!(
+			KittiesModule::add_oracle_authority(RuntimeOrigin::signed(1), 9),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 9));
+		mock::System::assert_last_event(Event::OracleAuthorityAdded { who: 9 }.into());
+		assert!(KittiesModule::is_oracle_authority(&9));
+		assert_noop!(
+			KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 9),
+			Error::<Test>::AlreadyOracleAuthority
+		);
+		assert_noop!(
+			KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 10),
+			Error::<Test>::TooManyOracleAuthorities
+		);
+
+		assert_noop!(
+			KittiesModule::remove_oracle_authority(RuntimeOrigin::signed(7), 7),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(KittiesModule::remove_oracle_authority(RuntimeOrigin::root(), 7));
+		mock::System::assert_last_event(Event::OracleAuthorityRemoved { who: 7 }.into());
+		assert_eq!(KittiesModule::oracle_authorities().to_vec(), vec![8, 9]);
+		assert_noop!(
+			KittiesModule::remove_oracle_authority(RuntimeOrigin::root(), 7),
+			Error::<Test>::NotOracleAuthority
+		);
+		assert_noop!(
+			KittiesModule::unsigned_extrinsic_with_signed_payload(
+				RuntimeOrigin::none(),
+				price_payload(7, b"27000.50"),
+				TestSignature(7, vec![]),
+			),
+			Error::<Test>::NotOracleAuthority
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_requires_oracle_authority() {
+	new_test_ext().execute_with(|| {
+		let call = |reporter: u64, signer: u64| {
+			let payload = price_payload(reporter, b"27000.50");
+			let signature = TestSignature(signer, payload.encode());
+			Call::unsigned_extrinsic_with_signed_payload { payload, signature }
+		};
+
+		assert!(KittiesModule::validate_unsigned(TransactionSource::External, &call(7, 7)).is_ok());
+		// the payload must be signed by its own key
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::External, &call(7, 9)),
+			Err(InvalidTransaction::BadProof.into())
+		);
+		// by a key of the authority set
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::External, &call(9, 9)),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+		// once per round
+		assert_ok!(submit_price(7, b"27000.50"));
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::External, &call(7, 7)),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert!(KittiesModule::validate_unsigned(TransactionSource::External, &call(8, 8)).is_ok());
+	});
+}
+
+#[test]
+fn watchlist_works() {
+	new_test_ext().execute_with(|| {
+		let symbol = |symbol: &[u8]| Symbol::try_from(symbol.to_vec()).unwrap();
+		assert_eq!(KittiesModule::watchlist().to_vec(), vec![btc_usdt()]);
+		assert_noop!(
+			KittiesModule::watch_symbol(RuntimeOrigin::signed(1), symbol(b"ETHUSDT")),
+			DispatchError::BadOrigin
+		);
+		for invalid in [&b""[..], b"ethusdt", b"ETH-USDT", b"ETH USDT"] {
+			assert_noop!(
+				KittiesModule::watch_symbol(RuntimeOrigin::root(), symbol(invalid)),
+				Error::<Test>::InvalidSymbol
+			);
+		}
+
+		assert_ok!(KittiesModule::watch_symbol(RuntimeOrigin::root(), symbol(b"ETHUSDT")));
+		mock::System::assert_last_event(Event::SymbolWatched { symbol: symbol(b"ETHUSDT") }.into());
+		assert!(KittiesModule::is_watched(&symbol(b"ETHUSDT")));
+		assert_noop!(
+			KittiesModule::watch_symbol(RuntimeOrigin::root(), symbol(b"ETHUSDT")),
+			Error::<Test>::AlreadyWatched
+		);
+		assert_ok!(KittiesModule::watch_symbol(RuntimeOrigin::root(), symbol(b"1INCHUSDT")));
+		assert_noop!(
+			KittiesModule::watch_symbol(RuntimeOrigin::root(), symbol(b"DOTUSDT")),
+			Error::<Test>::TooManySymbols
+		);
+
+		// unwatching drops the open round
+		assert_ok!(submit_price(7, b"27000.50"));
+		assert_noop!(
+			KittiesModule::unwatch_symbol(RuntimeOrigin::signed(1), btc_usdt()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(KittiesModule::unwatch_symbol(RuntimeOrigin::root(), btc_usdt()));
+		mock::System::assert_last_event(Event::SymbolUnwatched { symbol: btc_usdt() }.into());
+		assert_eq!(
+			KittiesModule::watchlist().to_vec(),
+			vec![symbol(b"ETHUSDT"), symbol(b"1INCHUSDT")]
+		);
+		assert_eq!(KittiesModule::round(btc_usdt()), Round::default());
+		assert!(KittiesModule::round_submissions(btc_usdt()).is_empty());
+		assert_noop!(
+			KittiesModule::unwatch_symbol(RuntimeOrigin::root(), btc_usdt()),
+			Error::<Test>::NotWatched
+		);
+	});
+}
+
+#[test]
+fn unwatched_symbols_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::unwatch_symbol(RuntimeOrigin::root(), btc_usdt()));
+
+		let payload = price_payload(7, b"27000.50");
+		let signature = TestSignature(7, payload.encode());
+		let call = Call::unsigned_extrinsic_with_signed_payload { payload, signature };
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Call.into())
+		);
+		assert_noop!(submit_price(7, b"27000.50"), Error::<Test>::NotWatched);
+	});
+}
+
+#[test]
+fn parse_price_works() {
+	let price = FixedU128::saturating_from_rational(270_005, 10);
+	for input in ["27000.50", "27000.50000000", "2.70005e4", "2.70005E+4", "270005e-1", "027000.5"]
+	{
+		assert_eq!(parse_price(input.as_bytes()), Ok(price), "{}", input);
+	}
+	assert_eq!(parse_price(b"5"), Ok(FixedU128::from_u32(5)));
+	assert_eq!(parse_price(b"5."), Ok(FixedU128::from_u32(5)));
+	assert_eq!(parse_price(b".5"), Ok(FixedU128::saturating_from_rational(1, 2)));
+	assert_eq!(parse_price(b"0.00000001"), Ok(FixedU128::from_inner(10_000_000_000)));
+	assert_eq!(parse_price(b"0"), Ok(FixedU128::from_inner(0)));
+	assert_eq!(parse_price(b"0e5000"), Ok(FixedU128::from_inner(0)));
+
+	// digits past 18 decimals are truncated
+	assert_eq!(parse_price(b"1e-18"), Ok(FixedU128::from_inner(1)));
+	assert_eq!(parse_price(b"1e-19"), Ok(FixedU128::from_inner(0)));
+	assert_eq!(parse_price(b"1e-5000"), Ok(FixedU128::from_inner(0)));
+	assert_eq!(
+		parse_price(b"1.99999999999999999999999"),
+		Ok(FixedU128::from_inner(1_999_999_999_999_999_999))
+	);
+	// the exponent applies before truncating
+	assert_eq!(parse_price(b"0.0000000000000000001e1"), Ok(FixedU128::from_inner(1)));
+}
+
+#[test]
+fn parse_price_fails() {
+	assert_eq!(
+		parse_price(b"340282366920938463463.374607431768211455"),
+		Ok(FixedU128::from_inner(u128::MAX))
+	);
+	assert_eq!(parse_price(b"340282366920938463463.374607431768211456"), Err(PriceError::Overflow));
+	assert_eq!(parse_price(b"340282366920938463464"), Err(PriceError::Overflow));
+	assert_eq!(
+		parse_price(b"1e20"),
+		Ok(FixedU128::saturating_from_integer(100_000_000_000_000_000_000u128))
+	);
+	assert_eq!(parse_price(b"1e21"), Err(PriceError::Overflow));
+	assert_eq!(parse_price(b"1e5000"), Err(PriceError::Overflow));
+
+	for input in [
+		"", ".", "-1", "+1", " 1", "1 ", "1.2.3", "1,000", "1e", "1e+", "1e-", "e5", "1ee2",
+		"1e2.5", "0x10", "NaN", "inf",
+	] {
+		assert_eq!(parse_price(input.as_bytes()), Err(PriceError::Malformed), "{}", input);
+	}
+}
+
+#[test]
+fn coin_price_info_deserializes() {
+	let info: CoinPriceInfo =
+		serde_json::from_str(r#"{"mins":5,"price":"27000.50000000"}"#).unwrap();
+	assert_eq!(info.price, FixedU128::saturating_from_rational(270_005, 10));
+	assert_eq!(format!("{:?}", info), "{ mins: 5, price: 27000.5 }");
+
+	let info: CoinPriceInfo = serde_json::from_str(r#"{"mins":5,"price":"27000"}"#).unwrap();
+	assert_eq!(format!("{:?}", info), "{ mins: 5, price: 27000 }");
+	let info: CoinPriceInfo = serde_json::from_str(r#"{"mins":5,"price":"0.00000001"}"#).unwrap();
+	assert_eq!(format!("{:?}", info), "{ mins: 5, price: 0.00000001 }");
+
+	assert!(serde_json::from_str::<CoinPriceInfo>(r#"{"mins":5,"price":"27,000"}"#).is_err());
+	assert!(serde_json::from_str::<CoinPriceInfo>(r#"{"mins":5,"price":"1e40"}"#).is_err());
+	assert!(serde_json::from_str::<CoinPriceInfo>(r#"{"mins":5,"price":27000}"#).is_err());
+}
+
+const BINANCE_BTC_USDT: &str = "https://data.binance.com/api/v3/avgPrice?symbol=BTCUSDT";
+const COINBASE_BTC_USDT: &str = "https://api.exchange.coinbase.com/products/BTC-USDT/ticker";
+const KRAKEN_BTC_USDT: &str = "https://api.kraken.com/0/public/Ticker?pair=BTCUSDT";
+const KRAKEN_TICKER: &[u8] = br#"{"error":[],"result":{"XBTUSDT":{"a":["27001.10000","1","1.000"],"b":["27000.90000","2","2.000"],"c":["27000.50000","0.00100000"],"v":["12.5","40.2"]}}}"#;
+
+/// Run `test` with offchain workers answering the `(uri, body)` requests, in order.
+fn with_http_responses(responses: &[(&str, &[u8])], test: impl FnOnce()) {
+	let (offchain, state) = TestOffchainExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	for (uri, body) in responses {
+		state.write().expect_request(PendingRequest {
+			method: "GET".into(),
+			uri: uri.to_string(),
+			headers: vec![("User-Agent".into(), "Substrate-Offchain-Worker".into())],
+			response: Some(body.to_vec()),
+			sent: true,
+			..Default::default()
+		});
+	}
+	ext.execute_with(test);
+}
+
+#[test]
+fn price_sources_build_urls() {
+	assert_eq!(Binance.url(b"BTCUSDT").unwrap(), BINANCE_BTC_USDT.as_bytes());
+	assert_eq!(Coinbase.url(b"BTCUSDT").unwrap(), COINBASE_BTC_USDT.as_bytes());
+	assert_eq!(Kraken.url(b"BTCUSDT").unwrap(), KRAKEN_BTC_USDT.as_bytes());
+	assert_eq!(
+		Coinbase.url(b"DOTUSD").unwrap(),
+		b"https://api.exchange.coinbase.com/products/DOT-USD/ticker".to_vec()
+	);
+	assert_eq!(
+		Coinbase.url(b"ETHBTC").unwrap(),
+		b"https://api.exchange.coinbase.com/products/ETH-BTC/ticker".to_vec()
+	);
+	// no known quote currency, or nothing before it
+	assert_eq!(Coinbase.url(b"BTCXYZ"), None);
+	assert_eq!(Coinbase.url(b"USDT"), None);
+
+	assert_eq!(price_source::source("kraken").unwrap().name(), "kraken");
+	assert!(price_source::source("ftx").is_none());
+}
+
+#[test]
+fn price_sources_parse_responses() {
+	let price = Some(FixedU128::saturating_from_rational(270_005, 10));
+	assert_eq!(Binance.parse(br#"{"mins":5,"price":"27000.50000000"}"#), price);
+	assert_eq!(
+		Coinbase.parse(
+			br#"{"ask":"27001.01","bid":"27000.99","volume":"1234.5","trade_id":1,"price":"27000.50","size":"0.01","time":"2023-01-01T00:00:00Z"}"#
+		),
+		price
+	);
+	assert_eq!(Kraken.parse(KRAKEN_TICKER), price);
+
+	assert_eq!(Binance.parse(br#"{"code":-1121,"msg":"Invalid symbol."}"#), None);
+	assert_eq!(Coinbase.parse(br#"{"message":"NotFound"}"#), None);
+	assert_eq!(Kraken.parse(br#"{"error":["EQuery:Unknown asset pair"]}"#), None);
+	assert_eq!(Kraken.parse(br#"{"error":[],"result":{}}"#), None);
+	assert_eq!(Kraken.parse(b"<html></html>"), None);
+}
+
+#[test]
+fn fetch_price_works() {
+	with_http_responses(&[(BINANCE_BTC_USDT, br#"{"mins":5,"price":"27000.50000000"}"#)], || {
+		assert_eq!(
+			KittiesModule::fetch_price(b"BTCUSDT"),
+			Some(FixedU128::saturating_from_rational(270_005, 10))
+		);
+	});
+}
+
+#[test]
+fn fetch_price_falls_back_to_next_source() {
+	let responses: &[(&str, &[u8])] = &[
+		(BINANCE_BTC_USDT, br#"{"code":-1121,"msg":"Invalid symbol."}"#),
+		(COINBASE_BTC_USDT, br#"{"message":"NotFound"}"#),
+		(KRAKEN_BTC_USDT, KRAKEN_TICKER),
+	];
+	with_http_responses(responses, || {
+		assert_eq!(
+			KittiesModule::fetch_price(b"BTCUSDT"),
+			Some(FixedU128::saturating_from_rational(270_005, 10))
+		);
+	});
+}
+
+#[test]
+fn fetch_price_uses_sources_from_local_storage() {
+	let responses: &[(&str, &[u8])] = &[
+		(KRAKEN_BTC_USDT, br#"{"error":["EService:Unavailable"]}"#),
+		(COINBASE_BTC_USDT, br#"{"price":"27100"}"#),
+	];
+	with_http_responses(responses, || {
+		// unknown names are skipped
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			price_source::SOURCES_KEY,
+			b"kraken, ftx,coinbase",
+		);
+		assert_eq!(KittiesModule::fetch_price(b"BTCUSDT"), Some(FixedU128::from_u32(27_100)));
+	});
+
+	with_http_responses(&[(KRAKEN_BTC_USDT, br#"{"error":["EService:Unavailable"]}"#)], || {
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			price_source::SOURCES_KEY,
+			b"kraken",
 		);
+		assert_eq!(KittiesModule::fetch_price(b"BTCUSDT"), None);
 	});
 }
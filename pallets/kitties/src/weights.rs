@@ -0,0 +1,1532 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_kitties
+//!
+//! Hand-estimated from the storage accesses of each call until the benchmarks are run on
+//! reference hardware. Regenerate this file with `./scripts/benchmark.sh pallet_kitties`,
+//! which runs the command below.
+
+// Command:
+
+// ./target/release/node-template
+
+// benchmark
+
+// pallet
+
+// --chain
+
+// dev
+
+// --execution
+
+// wasm
+
+// --wasm-execution
+
+// compiled
+
+// --pallet
+
+// pallet_kitties
+
+// --extrinsic
+
+// *
+
+// --steps
+
+// 50
+
+// --repeat
+
+// 20
+
+// --output
+
+// ./pallets/kitties/src/weights.rs
+
+// --template
+
+// .maintain/frame-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+
+	fn create_kitty() -> Weight;
+
+	fn breed() -> Weight;
+
+	fn transfer() -> Weight;
+
+	fn sale() -> Weight;
+
+	fn buy() -> Weight;
+
+	fn cancel_sale() -> Weight;
+
+	fn update_price() -> Weight;
+
+	fn offer_siring() -> Weight;
+
+	fn revoke_siring() -> Weight;
+
+	fn create_auction() -> Weight;
+
+	fn bid() -> Weight;
+
+	fn cancel_auction() -> Weight;
+
+	fn make_offer(o: u32, ) -> Weight;
+
+	fn withdraw_offer() -> Weight;
+
+	fn accept_offer(o: u32, ) -> Weight;
+
+	fn release(o: u32, ) -> Weight;
+
+	fn withdraw_treasury() -> Weight;
+
+	fn set_kitty_price() -> Weight;
+
+	fn approve() -> Weight;
+
+	fn set_approval_for_all() -> Weight;
+
+	fn transfer_from() -> Weight;
+
+	fn add_oracle_authority() -> Weight;
+
+	fn remove_oracle_authority() -> Weight;
+
+	fn watch_symbol() -> Weight;
+
+	fn unwatch_symbol() -> Weight;
+
+	fn unsigned_extrinsic_with_signed_payload() -> Weight;
+
+	fn reveal_births(b: u32, ) -> Weight;
+
+	fn settle_auctions(a: u32, ) -> Weight;
+
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+
+
+	// Storage: PalletKitties NextKittyId (r:1 w:1)
+
+	// Storage: PalletKitties KittyPrice (r:1 w:0)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties BirthsRevealedAt (r:1 w:1)
+
+	// Storage: PalletKitties OwnedKitties (r:1 w:1)
+
+	// Storage: PalletKitties PendingBirths (r:0 w:1)
+
+	// Storage: PalletKitties KittyOwner (r:0 w:1)
+
+	// Storage: PalletKitties KittyDeposits (r:0 w:1)
+
+
+	fn create_kitty() -> Weight {
+		Weight::from_ref_time(48_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties SiringOffers (r:2 w:2)
+
+	// Storage: PalletKitties KittyOwner (r:2 w:1)
+
+	// Storage: PalletKitties Auctions (r:2 w:0)
+
+	// Storage: PalletKitties NextKittyId (r:1 w:1)
+
+	// Storage: PalletKitties Kitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyPrice (r:1 w:0)
+
+	// Storage: System Account (r:3 w:3)
+
+	// Storage: PalletKitties BirthsRevealedAt (r:1 w:1)
+
+	// Storage: PalletKitties OwnedKitties (r:1 w:1)
+
+	// Storage: PalletKitties PendingBirths (r:0 w:1)
+
+	// Storage: PalletKitties KittyParents (r:0 w:1)
+
+	// Storage: PalletKitties KittyDeposits (r:0 w:1)
+
+
+	fn breed() -> Weight {
+		Weight::from_ref_time(97_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(15 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(14 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	fn transfer() -> Weight {
+		Weight::from_ref_time(41_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties KittyOnSale (r:1 w:1)
+
+
+	fn sale() -> Weight {
+		Weight::from_ref_time(27_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties KittyOnSale (r:1 w:1)
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	fn buy() -> Weight {
+		Weight::from_ref_time(68_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+
+
+	}
+
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties KittyOnSale (r:1 w:1)
+
+
+	fn cancel_sale() -> Weight {
+		Weight::from_ref_time(24_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties KittyOnSale (r:1 w:1)
+
+
+	fn update_price() -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+
+	fn offer_siring() -> Weight {
+		Weight::from_ref_time(26_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties SiringOffers (r:1 w:1)
+
+
+	fn revoke_siring() -> Weight {
+		Weight::from_ref_time(24_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Auctions (r:1 w:1)
+
+	// Storage: PalletKitties AuctionsEndingAt (r:1 w:1)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+
+	fn create_auction() -> Weight {
+		Weight::from_ref_time(34_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Auctions (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+
+	fn bid() -> Weight {
+		Weight::from_ref_time(47_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Auctions (r:1 w:1)
+
+	// Storage: PalletKitties AuctionsEndingAt (r:1 w:1)
+
+
+	fn cancel_auction() -> Weight {
+		Weight::from_ref_time(29_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Offers (r:1 w:1)
+
+	// Storage: System Account (r:1 w:1)
+
+
+	/// The range of component `o` is `[0, 32]`.
+
+	fn make_offer(o: u32, ) -> Weight {
+		Weight::from_ref_time(38_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(14_000_000 as u64).saturating_mul(o as u64))
+
+
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(o as u64)))
+
+
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(o as u64)))
+
+	}
+
+
+	// Storage: PalletKitties Offers (r:1 w:1)
+
+	// Storage: System Account (r:1 w:1)
+
+
+	fn withdraw_offer() -> Weight {
+		Weight::from_ref_time(31_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties Offers (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	/// The range of component `o` is `[0, 31]`.
+
+	fn accept_offer(o: u32, ) -> Weight {
+		Weight::from_ref_time(66_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(14_000_000 as u64).saturating_mul(o as u64))
+
+
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(o as u64)))
+
+
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(o as u64)))
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:1)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties KittyDeposits (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties Offers (r:1 w:1)
+
+	// Storage: PalletKitties OwnedKitties (r:1 w:1)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	/// The range of component `o` is `[0, 32]`.
+
+	fn release(o: u32, ) -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(14_000_000 as u64).saturating_mul(o as u64))
+
+
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(o as u64)))
+
+
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(o as u64)))
+
+	}
+
+
+	// Storage: System Account (r:2 w:2)
+
+
+	fn withdraw_treasury() -> Weight {
+		Weight::from_ref_time(36_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties KittyPrice (r:0 w:1)
+
+
+	fn set_kitty_price() -> Weight {
+		Weight::from_ref_time(14_000_000 as u64)
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	fn approve() -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties OperatorApprovals (r:0 w:1)
+
+
+	fn set_approval_for_all() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:2 w:1)
+
+	// Storage: PalletKitties Approvals (r:1 w:1)
+
+	// Storage: PalletKitties OperatorApprovals (r:1 w:0)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+
+	fn transfer_from() -> Weight {
+		Weight::from_ref_time(48_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties OracleAuthorities (r:1 w:1)
+
+
+	fn add_oracle_authority() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties OracleAuthorities (r:1 w:1)
+
+
+	fn remove_oracle_authority() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Watchlist (r:1 w:1)
+
+
+	fn watch_symbol() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Watchlist (r:1 w:1)
+
+	// Storage: PalletKitties Rounds (r:0 w:1)
+
+	// Storage: PalletKitties RoundSubmissions (r:0 w:1)
+
+
+	fn unwatch_symbol() -> Weight {
+		Weight::from_ref_time(21_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties OracleAuthorities (r:1 w:0)
+
+	// Storage: PalletKitties Watchlist (r:1 w:0)
+
+	// Storage: PalletKitties Rounds (r:1 w:1)
+
+	// Storage: PalletKitties RoundSubmissions (r:1 w:1)
+
+	// Storage: Timestamp Now (r:1 w:0)
+
+	// Storage: PalletKitties Prices (r:0 w:1)
+
+
+	fn unsigned_extrinsic_with_signed_payload() -> Weight {
+		Weight::from_ref_time(42_000_000 as u64)
+
+
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties BirthsRevealedAt (r:1 w:1)
+
+	// Storage: System BlockHash (r:1 w:0)
+
+	// Storage: PalletKitties PendingBirths (r:1 w:1)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Kitties (r:0 w:1)
+
+
+	/// The range of component `b` is `[0, 64]`.
+
+	fn reveal_births(b: u32, ) -> Weight {
+		Weight::from_ref_time(6_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(21_000_000 as u64).saturating_mul(b as u64))
+
+
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(b as u64)))
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(b as u64)))
+
+	}
+
+
+	// Storage: PalletKitties AuctionsEndingAt (r:1 w:1)
+
+	// Storage: PalletKitties Auctions (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	/// The range of component `a` is `[0, 64]`.
+
+	fn settle_auctions(a: u32, ) -> Weight {
+		Weight::from_ref_time(4_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(52_000_000 as u64).saturating_mul(a as u64))
+
+
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(a as u64)))
+
+
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+			.saturating_add(T::DbWeight::get().writes((9 as u64).saturating_mul(a as u64)))
+
+	}
+
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+
+	// Storage: PalletKitties NextKittyId (r:1 w:1)
+
+	// Storage: PalletKitties KittyPrice (r:1 w:0)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties BirthsRevealedAt (r:1 w:1)
+
+	// Storage: PalletKitties OwnedKitties (r:1 w:1)
+
+	// Storage: PalletKitties PendingBirths (r:0 w:1)
+
+	// Storage: PalletKitties KittyOwner (r:0 w:1)
+
+	// Storage: PalletKitties KittyDeposits (r:0 w:1)
+
+
+	fn create_kitty() -> Weight {
+		Weight::from_ref_time(48_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties SiringOffers (r:2 w:2)
+
+	// Storage: PalletKitties KittyOwner (r:2 w:1)
+
+	// Storage: PalletKitties Auctions (r:2 w:0)
+
+	// Storage: PalletKitties NextKittyId (r:1 w:1)
+
+	// Storage: PalletKitties Kitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyPrice (r:1 w:0)
+
+	// Storage: System Account (r:3 w:3)
+
+	// Storage: PalletKitties BirthsRevealedAt (r:1 w:1)
+
+	// Storage: PalletKitties OwnedKitties (r:1 w:1)
+
+	// Storage: PalletKitties PendingBirths (r:0 w:1)
+
+	// Storage: PalletKitties KittyParents (r:0 w:1)
+
+	// Storage: PalletKitties KittyDeposits (r:0 w:1)
+
+
+	fn breed() -> Weight {
+		Weight::from_ref_time(97_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(15 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(14 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	fn transfer() -> Weight {
+		Weight::from_ref_time(41_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties KittyOnSale (r:1 w:1)
+
+
+	fn sale() -> Weight {
+		Weight::from_ref_time(27_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties KittyOnSale (r:1 w:1)
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	fn buy() -> Weight {
+		Weight::from_ref_time(68_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(7 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+
+
+	}
+
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties KittyOnSale (r:1 w:1)
+
+
+	fn cancel_sale() -> Weight {
+		Weight::from_ref_time(24_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties KittyOnSale (r:1 w:1)
+
+
+	fn update_price() -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+
+	fn offer_siring() -> Weight {
+		Weight::from_ref_time(26_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties SiringOffers (r:1 w:1)
+
+
+	fn revoke_siring() -> Weight {
+		Weight::from_ref_time(24_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Auctions (r:1 w:1)
+
+	// Storage: PalletKitties AuctionsEndingAt (r:1 w:1)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+
+	fn create_auction() -> Weight {
+		Weight::from_ref_time(34_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Auctions (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+
+	fn bid() -> Weight {
+		Weight::from_ref_time(47_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Auctions (r:1 w:1)
+
+	// Storage: PalletKitties AuctionsEndingAt (r:1 w:1)
+
+
+	fn cancel_auction() -> Weight {
+		Weight::from_ref_time(29_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Offers (r:1 w:1)
+
+	// Storage: System Account (r:1 w:1)
+
+
+	/// The range of component `o` is `[0, 32]`.
+
+	fn make_offer(o: u32, ) -> Weight {
+		Weight::from_ref_time(38_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(14_000_000 as u64).saturating_mul(o as u64))
+
+
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(o as u64)))
+
+
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(o as u64)))
+
+	}
+
+
+	// Storage: PalletKitties Offers (r:1 w:1)
+
+	// Storage: System Account (r:1 w:1)
+
+
+	fn withdraw_offer() -> Weight {
+		Weight::from_ref_time(31_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties Offers (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	/// The range of component `o` is `[0, 31]`.
+
+	fn accept_offer(o: u32, ) -> Weight {
+		Weight::from_ref_time(66_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(14_000_000 as u64).saturating_mul(o as u64))
+
+
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(o as u64)))
+
+
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(o as u64)))
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:1)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties KittyDeposits (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties Offers (r:1 w:1)
+
+	// Storage: PalletKitties OwnedKitties (r:1 w:1)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	/// The range of component `o` is `[0, 32]`.
+
+	fn release(o: u32, ) -> Weight {
+		Weight::from_ref_time(44_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(14_000_000 as u64).saturating_mul(o as u64))
+
+
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(o as u64)))
+
+
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(o as u64)))
+
+	}
+
+
+	// Storage: System Account (r:2 w:2)
+
+
+	fn withdraw_treasury() -> Weight {
+		Weight::from_ref_time(36_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties KittyPrice (r:0 w:1)
+
+
+	fn set_kitty_price() -> Weight {
+		Weight::from_ref_time(14_000_000 as u64)
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	fn approve() -> Weight {
+		Weight::from_ref_time(25_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties OperatorApprovals (r:0 w:1)
+
+
+	fn set_approval_for_all() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Kitties (r:1 w:0)
+
+	// Storage: PalletKitties KittyOwner (r:2 w:1)
+
+	// Storage: PalletKitties Approvals (r:1 w:1)
+
+	// Storage: PalletKitties OperatorApprovals (r:1 w:0)
+
+	// Storage: PalletKitties Auctions (r:1 w:0)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+
+	fn transfer_from() -> Weight {
+		Weight::from_ref_time(48_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties OracleAuthorities (r:1 w:1)
+
+
+	fn add_oracle_authority() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties OracleAuthorities (r:1 w:1)
+
+
+	fn remove_oracle_authority() -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Watchlist (r:1 w:1)
+
+
+	fn watch_symbol() -> Weight {
+		Weight::from_ref_time(17_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties Watchlist (r:1 w:1)
+
+	// Storage: PalletKitties Rounds (r:0 w:1)
+
+	// Storage: PalletKitties RoundSubmissions (r:0 w:1)
+
+
+	fn unwatch_symbol() -> Weight {
+		Weight::from_ref_time(21_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties OracleAuthorities (r:1 w:0)
+
+	// Storage: PalletKitties Watchlist (r:1 w:0)
+
+	// Storage: PalletKitties Rounds (r:1 w:1)
+
+	// Storage: PalletKitties RoundSubmissions (r:1 w:1)
+
+	// Storage: Timestamp Now (r:1 w:0)
+
+	// Storage: PalletKitties Prices (r:0 w:1)
+
+
+	fn unsigned_extrinsic_with_signed_payload() -> Weight {
+		Weight::from_ref_time(42_000_000 as u64)
+
+
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+
+
+	}
+
+
+	// Storage: PalletKitties BirthsRevealedAt (r:1 w:1)
+
+	// Storage: System BlockHash (r:1 w:0)
+
+	// Storage: PalletKitties PendingBirths (r:1 w:1)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:0)
+
+	// Storage: PalletKitties Kitties (r:0 w:1)
+
+
+	/// The range of component `b` is `[0, 64]`.
+
+	fn reveal_births(b: u32, ) -> Weight {
+		Weight::from_ref_time(6_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(21_000_000 as u64).saturating_mul(b as u64))
+
+
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(b as u64)))
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(b as u64)))
+
+	}
+
+
+	// Storage: PalletKitties AuctionsEndingAt (r:1 w:1)
+
+	// Storage: PalletKitties Auctions (r:1 w:1)
+
+	// Storage: System Account (r:2 w:2)
+
+	// Storage: PalletKitties KittyOwner (r:1 w:1)
+
+	// Storage: PalletKitties OwnedKitties (r:2 w:2)
+
+	// Storage: PalletKitties KittyOnSale (r:0 w:1)
+
+	// Storage: PalletKitties SiringOffers (r:0 w:1)
+
+	// Storage: PalletKitties Approvals (r:0 w:1)
+
+
+	/// The range of component `a` is `[0, 64]`.
+
+	fn settle_auctions(a: u32, ) -> Weight {
+		Weight::from_ref_time(4_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(52_000_000 as u64).saturating_mul(a as u64))
+
+
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(a as u64)))
+
+
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+			.saturating_add(RocksDbWeight::get().writes((9 as u64).saturating_mul(a as u64)))
+
+	}
+
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API definition for `pallet_poe`.
+
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi {
+		/// All versions of the chain `claim` belongs to, oldest first.
+		fn lineage(claim: Vec<u8>) -> Vec<Vec<u8>>;
+
+		/// The newest version of the chain `claim` belongs to.
+		fn latest_version(claim: Vec<u8>) -> Option<Vec<u8>>;
+	}
+}
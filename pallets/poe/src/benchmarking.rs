@@ -6,11 +6,21 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+/// A full length claim for version `i` of a chain, distinct from the claims made of zeros.
+fn version<T: Config>(i: u32) -> BoundedVec<u8, T::MaxClaimLength> {
+	let mut claim = vec![1; T::MaxClaimLength::get() as usize];
+	claim[..4].copy_from_slice(&i.to_le_bytes());
+	BoundedVec::try_from(claim).unwrap()
+}
+
 benchmarks! {
+	// the claim was revoked by the caller, whose tombstone is checked and cleared
 	created_claim {
 		let d in 0 .. T::MaxClaimLength::get();
 		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
 		let caller: T::AccountId = whitelisted_caller();
+		assert!(Pallet::<T>::created_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone()).is_ok());
+		assert!(Pallet::<T>::revoke_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), RevocationReason::Other, None).is_ok());
 	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
 	verify {
 		assert_last_event::<T>(Event::ClaimCreated(caller, claim).into())
@@ -18,12 +28,15 @@ benchmarks! {
 
 	revoke_claim {
 		let d in 0 .. T::MaxClaimLength::get();
+		let s in 1 .. T::MaxClaimLength::get();
 		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let successor = BoundedVec::try_from(vec![1; s as usize]).unwrap();
 		let caller: T::AccountId = whitelisted_caller();
 		assert!(Pallet::<T>::created_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone()).is_ok());
-	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+		assert!(Pallet::<T>::created_claim(RawOrigin::Signed(caller.clone()).into(), successor.clone()).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), RevocationReason::Superseded, Some(successor.clone()))
 	verify {
-		assert_last_event::<T>(Event::ClaimRevoked(caller, claim).into())
+		assert_last_event::<T>(Event::ClaimRevoked(caller, claim, RevocationReason::Superseded, Some(successor)).into())
 	}
 
 	transfer_claim {
@@ -37,5 +50,25 @@ benchmarks! {
 		assert_last_event::<T>(Event::ClaimTransferred(caller, claim, to).into())
 	}
 
+	// the previous version is one short of `MaxVersions`, and the new version was revoked by the
+	// caller, whose tombstone is checked and cleared
+	create_claim_version {
+		let d in 0 .. T::MaxClaimLength::get();
+		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		let mut previous = version::<T>(0);
+		assert!(Pallet::<T>::created_claim(RawOrigin::Signed(caller.clone()).into(), previous.clone()).is_ok());
+		for i in 1 .. T::MaxVersions::get() - 1 {
+			let next = version::<T>(i);
+			assert!(Pallet::<T>::create_claim_version(RawOrigin::Signed(caller.clone()).into(), next.clone(), previous).is_ok());
+			previous = next;
+		}
+		assert!(Pallet::<T>::created_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone()).is_ok());
+		assert!(Pallet::<T>::revoke_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), RevocationReason::Other, None).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), previous.clone())
+	verify {
+		assert_last_event::<T>(Event::ClaimVersionCreated(caller, claim, previous).into())
+	}
+
 	impl_benchmark_test_suite!(PoeModule, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -24,6 +24,50 @@ pub mod pallet {
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	/// Why a claim was revoked.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum RevocationReason {
+		/// A newer version of the document replaces this claim.
+		Superseded,
+		/// The key or the document behind the claim is no longer trustworthy.
+		Compromised,
+		/// The claim was created by mistake.
+		Mistaken,
+		/// The claim had to be withdrawn for legal reasons.
+		Legal,
+		/// Any other reason.
+		Other,
+	}
+
+	/// What remains of a claim after it was revoked.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Tombstone<AccountId, BlockNumber, Claim> {
+		pub owner: AccountId,
+		pub created_at: BlockNumber,
+		pub revoked_at: BlockNumber,
+		pub reason: RevocationReason,
+		pub superseded_by: Option<Claim>,
+	}
+
+	/// Position of a claim in a chain of document versions.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct VersionInfo<Claim> {
+		/// The first version of the chain.
+		pub root: Claim,
+		pub previous: Option<Claim>,
+		pub next: Option<Claim>,
+		/// Version number, starting at 1 for the root.
+		pub version: u32,
+	}
+
+	pub type VersionInfoOf<T> = VersionInfo<BoundedVec<u8, <T as Config>::MaxClaimLength>>;
+
+	pub type TombstoneOf<T> = Tombstone<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BoundedVec<u8, <T as Config>::MaxClaimLength>,
+	>;
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -31,6 +75,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxClaimLength: Get<u32>;
 
+		/// The maximum number of versions in a document version chain.
+		#[pallet::constant]
+		type MaxVersions: Get<u32>;
+
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
@@ -47,12 +95,48 @@ pub mod pallet {
 		(T::AccountId, T::BlockNumber),
 	>;
 
+	/// Tombstones of revoked claims. Only the owner of a tombstone can create its claim again,
+	/// which clears the tombstone.
+	#[pallet::storage]
+	#[pallet::getter(fn revoked)]
+	pub type Revoked<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, TombstoneOf<T>>;
+
+	/// Links of every claim that is part of a version chain.
+	#[pallet::storage]
+	#[pallet::getter(fn versions)]
+	pub type Versions<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, VersionInfoOf<T>>;
+
+	/// Latest version and owner of each chain, keyed by the chain's root claim.
+	///
+	/// The chain stays with the owner of its root when its versions are transferred or revoked.
+	#[pallet::storage]
+	#[pallet::getter(fn chain_heads)]
+	pub type ChainHeads<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxClaimLength>,
+		(BoundedVec<u8, T::MaxClaimLength>, T::AccountId),
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		ClaimCreated(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
-		ClaimRevoked(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		ClaimRevoked(
+			T::AccountId,
+			BoundedVec<u8, T::MaxClaimLength>,
+			RevocationReason,
+			Option<BoundedVec<u8, T::MaxClaimLength>>,
+		),
 		ClaimTransferred(T::AccountId, BoundedVec<u8, T::MaxClaimLength>, T::AccountId),
+		/// A claim was created as the successor of an existing claim. [owner, claim, previous]
+		ClaimVersionCreated(
+			T::AccountId,
+			BoundedVec<u8, T::MaxClaimLength>,
+			BoundedVec<u8, T::MaxClaimLength>,
+		),
 	}
 
 	#[pallet::error]
@@ -61,6 +145,12 @@ pub mod pallet {
 		ClaimTooLang,
 		ClaimNotExist,
 		NotClaimOwner,
+		SupersedingClaimNotExist,
+		/// The previous version already has a successor, only the latest version can be extended.
+		NotLatestVersion,
+		VersionChainTooLong,
+		/// The claim belongs to a version chain, a revoked version can only be created in place.
+		ClaimAlreadyVersioned,
 	}
 
 	#[pallet::hooks]
@@ -76,11 +166,16 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 			ensure!(!Proofs::<T>::contains_key(&bounded_claim), Error::<T>::ProofAlreadyExist);
+			// a revoked claim keeps its place in a version chain, which stays its owner's
+			if let Some(tombstone) = Revoked::<T>::get(&bounded_claim) {
+				ensure!(tombstone.owner == sender, Error::<T>::NotClaimOwner);
+			}
 
 			Proofs::<T>::insert(
 				&bounded_claim,
 				(sender.clone(), frame_system::Pallet::<T>::block_number()),
 			);
+			Revoked::<T>::remove(&bounded_claim);
 
 			Self::deposit_event(Event::<T>::ClaimCreated(sender, bounded_claim));
 
@@ -88,20 +183,48 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::revoke_claim(bounded_claim.len() as u32))]
+		#[pallet::weight(T::WeightInfo::revoke_claim(
+			bounded_claim.len() as u32,
+			superseded_by.as_ref().map_or(0, |claim| claim.len() as u32),
+		))]
 		pub fn revoke_claim(
 			origin: OriginFor<T>,
 			bounded_claim: BoundedVec<u8, T::MaxClaimLength>,
+			reason: RevocationReason,
+			superseded_by: Option<BoundedVec<u8, T::MaxClaimLength>>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let (owner, _) = Proofs::<T>::get(&bounded_claim).ok_or(Error::<T>::ClaimNotExist)?;
+			let (owner, created_at) =
+				Proofs::<T>::get(&bounded_claim).ok_or(Error::<T>::ClaimNotExist)?;
 
 			ensure!(owner == sender, Error::<T>::NotClaimOwner);
 
+			if let Some(ref successor) = superseded_by {
+				ensure!(
+					successor != &bounded_claim && Proofs::<T>::contains_key(successor),
+					Error::<T>::SupersedingClaimNotExist
+				);
+			}
+
 			Proofs::<T>::remove(&bounded_claim);
+			Revoked::<T>::insert(
+				&bounded_claim,
+				Tombstone {
+					owner,
+					created_at,
+					revoked_at: frame_system::Pallet::<T>::block_number(),
+					reason,
+					superseded_by: superseded_by.clone(),
+				},
+			);
 
-			Self::deposit_event(Event::<T>::ClaimRevoked(sender, bounded_claim));
+			Self::deposit_event(Event::<T>::ClaimRevoked(
+				sender,
+				bounded_claim,
+				reason,
+				superseded_by,
+			));
 
 			Ok(().into())
 		}
@@ -128,5 +251,101 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::create_claim_version(bounded_claim.len() as u32))]
+		pub fn create_claim_version(
+			origin: OriginFor<T>,
+			bounded_claim: BoundedVec<u8, T::MaxClaimLength>,
+			previous: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(!Proofs::<T>::contains_key(&bounded_claim), Error::<T>::ProofAlreadyExist);
+			// a revoked version keeps its place in its chain
+			ensure!(
+				!Versions::<T>::contains_key(&bounded_claim),
+				Error::<T>::ClaimAlreadyVersioned
+			);
+			if let Some(tombstone) = Revoked::<T>::get(&bounded_claim) {
+				ensure!(tombstone.owner == sender, Error::<T>::NotClaimOwner);
+			}
+
+			let (owner, _) = Proofs::<T>::get(&previous).ok_or(Error::<T>::ClaimNotExist)?;
+
+			// a standalone claim becomes the root of a new chain
+			let mut previous_info = Versions::<T>::get(&previous).unwrap_or(VersionInfo {
+				root: previous.clone(),
+				previous: None,
+				next: None,
+				version: 1,
+			});
+			let chain_owner = match ChainHeads::<T>::get(&previous_info.root) {
+				Some((_, chain_owner)) => chain_owner,
+				None => owner,
+			};
+			ensure!(chain_owner == sender, Error::<T>::NotClaimOwner);
+			ensure!(previous_info.next.is_none(), Error::<T>::NotLatestVersion);
+			let version = previous_info.version.saturating_add(1);
+			ensure!(version <= T::MaxVersions::get(), Error::<T>::VersionChainTooLong);
+
+			Proofs::<T>::insert(
+				&bounded_claim,
+				(sender.clone(), frame_system::Pallet::<T>::block_number()),
+			);
+			Versions::<T>::insert(
+				&bounded_claim,
+				VersionInfo {
+					root: previous_info.root.clone(),
+					previous: Some(previous.clone()),
+					next: None,
+					version,
+				},
+			);
+			ChainHeads::<T>::insert(&previous_info.root, (&bounded_claim, &sender));
+			Revoked::<T>::remove(&bounded_claim);
+			previous_info.next = Some(bounded_claim.clone());
+			Versions::<T>::insert(&previous, previous_info);
+
+			Self::deposit_event(Event::<T>::ClaimVersionCreated(sender, bounded_claim, previous));
+
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// All versions of the chain `claim` belongs to, oldest first.
+		///
+		/// A claim outside of any chain is its own lineage. Unknown claims have none.
+		pub fn lineage(claim: Vec<u8>) -> Vec<Vec<u8>> {
+			let claim = match BoundedVec::<u8, T::MaxClaimLength>::try_from(claim) {
+				Ok(claim) => claim,
+				Err(_) => return Vec::new(),
+			};
+
+			let mut current = match Versions::<T>::get(&claim) {
+				Some(info) => Some(info.root),
+				None if Proofs::<T>::contains_key(&claim) || Revoked::<T>::contains_key(&claim) =>
+					return vec![claim.into_inner()],
+				None => return Vec::new(),
+			};
+
+			let mut lineage = Vec::new();
+			while let Some(version) = current {
+				current = Versions::<T>::get(&version).and_then(|info| info.next);
+				lineage.push(version.into_inner());
+			}
+			lineage
+		}
+
+		/// The newest version of the chain `claim` belongs to.
+		pub fn latest_version(claim: Vec<u8>) -> Option<Vec<u8>> {
+			let claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim).ok()?;
+			match Versions::<T>::get(&claim) {
+				Some(info) => ChainHeads::<T>::get(&info.root).map(|(head, _)| head.into_inner()),
+				None if Proofs::<T>::contains_key(&claim) || Revoked::<T>::contains_key(&claim) =>
+					Some(claim.into_inner()),
+				None => None,
+			}
+		}
 	}
 }
@@ -52,6 +52,7 @@ impl system::Config for Test {
 impl pallet_poe::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxClaimLength = ConstU32<10>;
+	type MaxVersions = ConstU32<3>;
 	type WeightInfo = ();
 }
 
@@ -35,7 +35,143 @@ fn revoke_claim_works() {
 		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
 		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), claim.clone());
 
-		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::revoke_claim(
+			RuntimeOrigin::signed(1),
+			claim.clone(),
+			RevocationReason::Mistaken,
+			None
+		));
+
+		assert_eq!(Proofs::<mock::Test>::get(&claim), None);
+		assert_eq!(
+			Revoked::<mock::Test>::get(&claim),
+			Some(Tombstone {
+				owner: 1,
+				created_at: 0,
+				revoked_at: 0,
+				reason: RevocationReason::Mistaken,
+				superseded_by: None,
+			})
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_with_superseding_claim_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let successor = BoundedVec::try_from(vec![0, 2]).unwrap();
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), claim.clone());
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), successor.clone());
+
+		System::set_block_number(2);
+		assert_ok!(PoeModule::revoke_claim(
+			RuntimeOrigin::signed(1),
+			claim.clone(),
+			RevocationReason::Superseded,
+			Some(successor.clone())
+		));
+
+		assert_eq!(
+			Revoked::<mock::Test>::get(&claim),
+			Some(Tombstone {
+				owner: 1,
+				created_at: 1,
+				revoked_at: 2,
+				reason: RevocationReason::Superseded,
+				superseded_by: Some(successor.clone()),
+			})
+		);
+		System::assert_last_event(
+			Event::ClaimRevoked(1, claim, RevocationReason::Superseded, Some(successor)).into(),
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_failed_when_superseding_claim_is_not_exist() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), claim.clone());
+
+		assert_noop!(
+			PoeModule::revoke_claim(
+				RuntimeOrigin::signed(1),
+				claim.clone(),
+				RevocationReason::Superseded,
+				Some(BoundedVec::try_from(vec![0, 2]).unwrap())
+			),
+			Error::<Test>::SupersedingClaimNotExist
+		);
+		assert_noop!(
+			PoeModule::revoke_claim(
+				RuntimeOrigin::signed(1),
+				claim.clone(),
+				RevocationReason::Superseded,
+				Some(claim.clone())
+			),
+			Error::<Test>::SupersedingClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn create_claim_works_when_claim_is_revoked() {
+	new_test_ext().execute_with(|| {
+		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), claim.clone());
+		let _ = PoeModule::revoke_claim(
+			mock::RuntimeOrigin::signed(1),
+			claim.clone(),
+			RevocationReason::Compromised,
+			None,
+		);
+
+		// only the owner of the tombstone can create the claim again, which clears the tombstone
+		assert_noop!(
+			PoeModule::created_claim(RuntimeOrigin::signed(2), claim.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::created_claim(RuntimeOrigin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<mock::Test>::get(&claim), Some((1, 0)));
+		assert_eq!(Revoked::<mock::Test>::get(&claim), None);
+	});
+}
+
+#[test]
+fn create_claim_failed_when_revoked_chain_head_is_taken_over() {
+	new_test_ext().execute_with(|| {
+		let v1: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![1]).unwrap();
+		let v2: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![2]).unwrap();
+		let v3: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![3]).unwrap();
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), v1.clone());
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			v2.clone(),
+			v1.clone()
+		));
+		assert_ok!(PoeModule::revoke_claim(
+			RuntimeOrigin::signed(1),
+			v2.clone(),
+			RevocationReason::Compromised,
+			None
+		));
+
+		// another account can neither take the head of the chain nor extend it
+		assert_noop!(
+			PoeModule::created_claim(RuntimeOrigin::signed(2), v2.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::create_claim_version(RuntimeOrigin::signed(2), v3.clone(), v2.clone()),
+			Error::<Test>::ClaimNotExist
+		);
+		assert!(Revoked::<mock::Test>::get(&v2).is_some());
+		assert_eq!(PoeModule::latest_version(v1.to_vec()), Some(v2.to_vec()));
 	});
 }
 
@@ -45,7 +181,12 @@ fn create_claim_failed_when_is_not_exist() {
 		let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
 
 		assert_noop!(
-			PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim.clone()),
+			PoeModule::revoke_claim(
+				RuntimeOrigin::signed(1),
+				claim.clone(),
+				RevocationReason::Other,
+				None
+			),
 			Error::<Test>::ClaimNotExist
 		);
 	});
@@ -58,7 +199,12 @@ fn create_claim_failed_with_wrong_owner() {
 		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), claim.clone());
 
 		assert_noop!(
-			PoeModule::revoke_claim(RuntimeOrigin::signed(2), claim.clone()),
+			PoeModule::revoke_claim(
+				RuntimeOrigin::signed(2),
+				claim.clone(),
+				RevocationReason::Other,
+				None
+			),
 			Error::<Test>::NotClaimOwner
 		);
 	});
@@ -103,3 +249,201 @@ fn transfer_claim_works() {
 		);
 	});
 }
+
+#[test]
+fn create_claim_version_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let v1: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![1]).unwrap();
+		let v2: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![2]).unwrap();
+		let v3: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![3]).unwrap();
+		assert_ok!(PoeModule::created_claim(RuntimeOrigin::signed(1), v1.clone()));
+
+		// a standalone claim is its own lineage
+		assert_eq!(PoeModule::lineage(v1.to_vec()), vec![v1.to_vec()]);
+		assert_eq!(PoeModule::latest_version(v1.to_vec()), Some(v1.to_vec()));
+
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			v2.clone(),
+			v1.clone()
+		));
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			v3.clone(),
+			v2.clone()
+		));
+		System::assert_last_event(Event::ClaimVersionCreated(1, v3.clone(), v2.clone()).into());
+
+		assert_eq!(Proofs::<mock::Test>::get(&v3), Some((1, 1)));
+		assert_eq!(
+			Versions::<mock::Test>::get(&v2),
+			Some(VersionInfo {
+				root: v1.clone(),
+				previous: Some(v1.clone()),
+				next: Some(v3.clone()),
+				version: 2,
+			})
+		);
+		assert_eq!(ChainHeads::<mock::Test>::get(&v1), Some((v3.clone(), 1)));
+
+		let lineage = vec![v1.to_vec(), v2.to_vec(), v3.to_vec()];
+		for claim in [&v1, &v2, &v3] {
+			assert_eq!(PoeModule::lineage(claim.to_vec()), lineage);
+			assert_eq!(PoeModule::latest_version(claim.to_vec()), Some(v3.to_vec()));
+		}
+		assert_eq!(PoeModule::lineage(vec![4]), Vec::<Vec<u8>>::new());
+		assert_eq!(PoeModule::latest_version(vec![4]), None);
+	});
+}
+
+#[test]
+fn create_claim_version_failed_with_invalid_previous() {
+	new_test_ext().execute_with(|| {
+		let v1 = BoundedVec::try_from(vec![1]).unwrap();
+		let v2 = BoundedVec::try_from(vec![2]).unwrap();
+		let v3 = BoundedVec::try_from(vec![3]).unwrap();
+
+		assert_noop!(
+			PoeModule::create_claim_version(RuntimeOrigin::signed(1), v2.clone(), v1.clone()),
+			Error::<Test>::ClaimNotExist
+		);
+
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), v1.clone());
+		assert_noop!(
+			PoeModule::create_claim_version(RuntimeOrigin::signed(2), v2.clone(), v1.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::create_claim_version(RuntimeOrigin::signed(1), v1.clone(), v1.clone()),
+			Error::<Test>::ProofAlreadyExist
+		);
+
+		// only the head of a chain can be extended
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			v2.clone(),
+			v1.clone()
+		));
+		assert_noop!(
+			PoeModule::create_claim_version(RuntimeOrigin::signed(1), v3.clone(), v1.clone()),
+			Error::<Test>::NotLatestVersion
+		);
+	});
+}
+
+#[test]
+fn create_claim_version_failed_when_chain_is_too_long() {
+	new_test_ext().execute_with(|| {
+		let claims: Vec<BoundedVec<u8, <mock::Test as Config>::MaxClaimLength>> =
+			(0..4).map(|i| BoundedVec::try_from(vec![i]).unwrap()).collect();
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), claims[0].clone());
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			claims[1].clone(),
+			claims[0].clone()
+		));
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			claims[2].clone(),
+			claims[1].clone()
+		));
+
+		// MaxVersions is 3 in the mock runtime
+		assert_noop!(
+			PoeModule::create_claim_version(
+				RuntimeOrigin::signed(1),
+				claims[3].clone(),
+				claims[2].clone()
+			),
+			Error::<Test>::VersionChainTooLong
+		);
+	});
+}
+
+#[test]
+fn create_claim_version_failed_when_claim_was_revoked_from_a_chain() {
+	new_test_ext().execute_with(|| {
+		let v1: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![1]).unwrap();
+		let v2: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![2]).unwrap();
+		let other: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![3]).unwrap();
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), v1.clone());
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), other.clone());
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			v2.clone(),
+			v1.clone()
+		));
+		assert_ok!(PoeModule::revoke_claim(
+			RuntimeOrigin::signed(1),
+			v2.clone(),
+			RevocationReason::Mistaken,
+			None
+		));
+
+		// moving the revoked version to another chain would break its old chain
+		assert_noop!(
+			PoeModule::create_claim_version(RuntimeOrigin::signed(1), v2.clone(), other.clone()),
+			Error::<Test>::ClaimAlreadyVersioned
+		);
+
+		// created again in place, it is back in its chain
+		assert_ok!(PoeModule::created_claim(RuntimeOrigin::signed(1), v2.clone()));
+		assert_eq!(Revoked::<mock::Test>::get(&v2), None);
+		assert_eq!(PoeModule::lineage(v2.to_vec()), vec![v1.to_vec(), v2.to_vec()]);
+	});
+}
+
+#[test]
+fn create_claim_version_failed_when_chain_belongs_to_another_account() {
+	new_test_ext().execute_with(|| {
+		let v1: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![1]).unwrap();
+		let v2: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![2]).unwrap();
+		let v3: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![3]).unwrap();
+		let revoked: BoundedVec<u8, <mock::Test as Config>::MaxClaimLength> =
+			BoundedVec::try_from(vec![4]).unwrap();
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), v1.clone());
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			v2.clone(),
+			v1.clone()
+		));
+
+		// holding the head of the chain doesn't make its new owner the owner of the chain
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), v2.clone(), 2));
+		assert_noop!(
+			PoeModule::create_claim_version(RuntimeOrigin::signed(2), v3.clone(), v2.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+
+		// nor can another account take a revoked claim as a new version
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(1), revoked.clone());
+		assert_ok!(PoeModule::revoke_claim(
+			RuntimeOrigin::signed(1),
+			revoked.clone(),
+			RevocationReason::Other,
+			None
+		));
+		let _ = PoeModule::created_claim(mock::RuntimeOrigin::signed(2), v3.clone());
+		assert_noop!(
+			PoeModule::create_claim_version(RuntimeOrigin::signed(2), revoked.clone(), v3.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::create_claim_version(
+			RuntimeOrigin::signed(1),
+			revoked.clone(),
+			v2.clone()
+		));
+		assert_eq!(ChainHeads::<mock::Test>::get(&v1), Some((revoked.clone(), 1)));
+	});
+}
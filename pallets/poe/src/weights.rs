@@ -15,12 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_poe
+//! Weights for pallet_poe
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-06-26, STEPS: `20`, REPEAT: 10, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! HOSTNAME: `suntiebingdeMacBook-Pro.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! Hand-edited, not the output of a benchmark run. The times of `created_claim`,
+//! `revoke_claim` (over `d`) and `transfer_claim` come from a run of 2023-06-26 (STEPS: `20`,
+//! REPEAT: 10, CHAIN: Some("dev")). Their storage accesses, the successor length `s` of
+//! `revoke_claim` and all of `create_claim_version` are estimates. Regenerate this file with
+//! `./scripts/benchmark.sh pallet_poe`, which runs the command below.
 
 // Executed Command:
 
@@ -81,10 +82,12 @@ pub trait WeightInfo {
 
 	fn created_claim(d: u32, ) -> Weight;
 
-	fn revoke_claim(d: u32, ) -> Weight;
+	fn revoke_claim(d: u32, s: u32, ) -> Weight;
 
 	fn transfer_claim(d: u32, ) -> Weight;
 
+	fn create_claim_version(d: u32, ) -> Weight;
+
 }
 
 /// Weights for pallet_poe using the Substrate node and recommended hardware.
@@ -96,6 +99,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
 	// Storage: PoeModule Proofs (r:1 w:1)
 
+	// Storage: PoeModule Revoked (r:1 w:1)
+
 
 	/// The range of component `d` is `[0, 512]`.
 
@@ -106,33 +111,39 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_ref_time(115_288 as u64).saturating_mul(d as u64))
 
 
-			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
 
 
 
-			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
 
 
 	}
 
 
-	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule Proofs (r:2 w:1)
+
+	// Storage: PoeModule Revoked (r:0 w:1)
 
 
 	/// The range of component `d` is `[0, 512]`.
 
-	fn revoke_claim(d: u32, ) -> Weight {
+	/// The range of component `s` is `[1, 512]`.
+
+	fn revoke_claim(d: u32, s: u32, ) -> Weight {
 		Weight::from_ref_time(375_000_000 as u64)
 
 			// Standard Error: 2_917
 			.saturating_add(Weight::from_ref_time(252_344 as u64).saturating_mul(d as u64))
 
+			.saturating_add(Weight::from_ref_time(115_288 as u64).saturating_mul(s as u64))
 
-			.saturating_add(T::DbWeight::get().reads(1 as u64))
 
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
 
 
-			.saturating_add(T::DbWeight::get().writes(1 as u64))
+
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
 
 
 	}
@@ -159,6 +170,33 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
 	}
 
+
+	// Storage: PoeModule Proofs (r:2 w:1)
+
+	// Storage: PoeModule Versions (r:2 w:2)
+
+	// Storage: PoeModule Revoked (r:1 w:1)
+
+	// Storage: PoeModule ChainHeads (r:1 w:1)
+
+
+	/// The range of component `d` is `[0, 512]`.
+
+	fn create_claim_version(d: u32, ) -> Weight {
+		Weight::from_ref_time(395_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(259_588 as u64).saturating_mul(d as u64))
+
+
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+
+
+
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+
+
+	}
+
 }
 
 // For backwards compatibility and tests
@@ -167,6 +205,8 @@ impl WeightInfo for () {
 
 	// Storage: PoeModule Proofs (r:1 w:1)
 
+	// Storage: PoeModule Revoked (r:1 w:1)
+
 
 	/// The range of component `d` is `[0, 512]`.
 
@@ -177,33 +217,39 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_ref_time(115_288 as u64).saturating_mul(d as u64))
 
 
-			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
 
 
 
-			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
 
 
 	}
 
 
-	// Storage: PoeModule Proofs (r:1 w:1)
+	// Storage: PoeModule Proofs (r:2 w:1)
+
+	// Storage: PoeModule Revoked (r:0 w:1)
 
 
 	/// The range of component `d` is `[0, 512]`.
 
-	fn revoke_claim(d: u32, ) -> Weight {
+	/// The range of component `s` is `[1, 512]`.
+
+	fn revoke_claim(d: u32, s: u32, ) -> Weight {
 		Weight::from_ref_time(375_000_000 as u64)
 
 			// Standard Error: 2_917
 			.saturating_add(Weight::from_ref_time(252_344 as u64).saturating_mul(d as u64))
 
+			.saturating_add(Weight::from_ref_time(115_288 as u64).saturating_mul(s as u64))
 
-			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
 
 
-			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
 
 
 	}
@@ -230,4 +276,31 @@ impl WeightInfo for () {
 
 	}
 
+
+	// Storage: PoeModule Proofs (r:2 w:1)
+
+	// Storage: PoeModule Versions (r:2 w:2)
+
+	// Storage: PoeModule Revoked (r:1 w:1)
+
+	// Storage: PoeModule ChainHeads (r:1 w:1)
+
+
+	/// The range of component `d` is `[0, 512]`.
+
+	fn create_claim_version(d: u32, ) -> Weight {
+		Weight::from_ref_time(395_000_000 as u64)
+
+			.saturating_add(Weight::from_ref_time(259_588 as u64).saturating_mul(d as u64))
+
+
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+
+
+
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+
+
+	}
+
 }
\ No newline at end of file
@@ -0,0 +1,238 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Standalone verifier for `pallet_poe` claim proofs.
+//!
+//! A [`ClaimProof`] bundles a block header, the GRANDPA justification finalizing that header
+//! and a storage read proof of the claim's `Proofs` entry. Anybody holding a trusted GRANDPA
+//! authority set (for example the genesis set) can check the bundle without running a node.
+//!
+//! The verifier trusts a single, fixed [`AuthoritySet`] and does not follow authority set
+//! changes. Blocks finalized by a later set are rejected with [`Error::UnknownAuthority`] or
+//! [`Error::BadSignature`], their proofs need the set that finalized them.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::hashing::{blake2_128, twox_128};
+use sp_finality_grandpa::{AuthorityId, AuthorityList, AuthoritySignature, AuthorityWeight, SetId};
+use sp_runtime::{traits::Header as HeaderT, RuntimeDebug};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	prelude::*,
+};
+use sp_trie::{LayoutV1, StorageProof};
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the storage item holding claims in `pallet_poe`.
+const PROOFS_STORAGE_NAME: &[u8] = b"Proofs";
+
+/// Evidence that a claim was (or was not) stored at the block described by `header`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ClaimProof<Header> {
+	/// The raw claim bytes, as passed to `created_claim`.
+	pub claim: Vec<u8>,
+	/// Header of the block the proof was taken at.
+	pub header: Header,
+	/// SCALE-encoded GRANDPA justification finalizing `header`, if the node had one.
+	pub justification: Option<Vec<u8>>,
+	/// Trie nodes proving the `Proofs` entry of `claim` against `header.state_root`.
+	pub proof: Vec<Vec<u8>>,
+}
+
+/// A GRANDPA authority set the verifier trusts.
+///
+/// Only justifications signed by this exact set are accepted, hand-over proofs to later sets
+/// are not followed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AuthoritySet {
+	pub set_id: SetId,
+	pub authorities: AuthorityList,
+}
+
+impl AuthoritySet {
+	/// The authority set from the chain spec, which always has id `0`.
+	pub fn genesis(authorities: AuthorityList) -> Self {
+		Self { set_id: 0, authorities }
+	}
+
+	fn weight_of(&self, id: &AuthorityId) -> Option<AuthorityWeight> {
+		self.authorities.iter().find(|(a, _)| a == id).map(|(_, w)| *w)
+	}
+
+	/// Weight needed to finalize a block, as computed by `finality_grandpa::VoterSet`.
+	///
+	/// `0` for a set without weight, which can't finalize anything.
+	fn threshold(&self) -> AuthorityWeight {
+		let total = self.authorities.iter().fold(0u64, |acc, (_, w)| acc.saturating_add(*w));
+		total - total.saturating_sub(1) / 3
+	}
+}
+
+/// A GRANDPA commit for blocks with the given header type.
+pub type Commit<Header> = finality_grandpa::Commit<
+	<Header as HeaderT>::Hash,
+	<Header as HeaderT>::Number,
+	AuthoritySignature,
+	AuthorityId,
+>;
+
+/// Mirror of `sc_finality_grandpa::GrandpaJustification`, which is not available in `no_std`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct GrandpaJustification<Header: HeaderT> {
+	pub round: u64,
+	pub commit: Commit<Header>,
+	pub votes_ancestries: Vec<Header>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Error {
+	/// The trusted authority set is empty or has no weight, so nothing can be final.
+	EmptyAuthoritySet,
+	/// The bundle carries no justification, so finality cannot be checked.
+	MissingJustification,
+	/// The justification could not be decoded.
+	InvalidJustification,
+	/// The justification finalizes a different block than the bundled header.
+	JustificationTargetMismatch,
+	/// A precommit was signed by a key outside of the trusted authority set.
+	UnknownAuthority,
+	/// The same authority signed more than one precommit.
+	DuplicateVote,
+	/// A precommit signature does not verify for the given round and set id.
+	BadSignature,
+	/// A precommit targets a block that does not descend from the bundled header.
+	InvalidAncestry,
+	/// The signed precommits do not reach the supermajority threshold.
+	NotEnoughWeight,
+	/// The storage proof is incomplete or does not match the state root.
+	InvalidStorageProof,
+	/// The stored value could not be decoded into the requested type.
+	InvalidValue,
+}
+
+/// Storage prefix shared by all `Proofs` entries of the pallet instance named `pallet_name`.
+pub fn proofs_storage_prefix(pallet_name: &[u8]) -> Vec<u8> {
+	let mut prefix = Vec::with_capacity(32);
+	prefix.extend_from_slice(&twox_128(pallet_name));
+	prefix.extend_from_slice(&twox_128(PROOFS_STORAGE_NAME));
+	prefix
+}
+
+/// Storage key of the `Proofs` entry for `claim` in the pallet instance named `pallet_name`.
+///
+/// `Proofs` is a `Blake2_128Concat` map keyed by the SCALE-encoded claim.
+pub fn claim_storage_key(pallet_name: &[u8], claim: &[u8]) -> Vec<u8> {
+	let encoded_claim = claim.to_vec().encode();
+	let mut key = proofs_storage_prefix(pallet_name);
+	key.reserve(16 + encoded_claim.len());
+	key.extend_from_slice(&blake2_128(&encoded_claim));
+	key.extend_from_slice(&encoded_claim);
+	key
+}
+
+/// Recover the claim bytes from a full `Proofs` storage key.
+pub fn claim_from_storage_key(key: &[u8]) -> Option<Vec<u8>> {
+	let encoded_claim = key.get(32 + 16..)?;
+	Vec::<u8>::decode(&mut &encoded_claim[..]).ok()
+}
+
+/// Check `proof` against `authorities` and return the decoded `Proofs` value of the claim.
+///
+/// `Ok(None)` means the header is final and the claim was provably absent at that block.
+pub fn verify<Header, V>(
+	pallet_name: &[u8],
+	proof: &ClaimProof<Header>,
+	authorities: &AuthoritySet,
+) -> Result<Option<V>, Error>
+where
+	Header: HeaderT,
+	V: Decode,
+{
+	let justification = proof.justification.as_ref().ok_or(Error::MissingJustification)?;
+	verify_justification(&proof.header, justification, authorities)?;
+
+	let value = read_storage_proof::<Header>(
+		proof.header.state_root(),
+		proof.proof.clone(),
+		&claim_storage_key(pallet_name, &proof.claim),
+	)?;
+	value
+		.map(|v| V::decode(&mut &v[..]).map_err(|_| Error::InvalidValue))
+		.transpose()
+}
+
+/// Check that `encoded` is a valid GRANDPA justification of `header` signed by `authorities`.
+pub fn verify_justification<Header: HeaderT>(
+	header: &Header,
+	encoded: &[u8],
+	authorities: &AuthoritySet,
+) -> Result<(), Error> {
+	// an empty set would justify any header with an empty commit
+	let threshold = authorities.threshold();
+	if threshold == 0 {
+		return Err(Error::EmptyAuthoritySet)
+	}
+
+	let justification = GrandpaJustification::<Header>::decode(&mut &encoded[..])
+		.map_err(|_| Error::InvalidJustification)?;
+
+	let hash = header.hash();
+	if justification.commit.target_hash != hash ||
+		justification.commit.target_number != *header.number()
+	{
+		return Err(Error::JustificationTargetMismatch)
+	}
+
+	let ancestry: BTreeMap<Header::Hash, &Header> =
+		justification.votes_ancestries.iter().map(|h| (h.hash(), h)).collect();
+	let mut voters = BTreeSet::new();
+	let mut weight: AuthorityWeight = 0;
+
+	for signed in justification.commit.precommits.iter() {
+		let voter_weight = authorities.weight_of(&signed.id).ok_or(Error::UnknownAuthority)?;
+		if !voters.insert(signed.id.clone()) {
+			return Err(Error::DuplicateVote)
+		}
+
+		// a precommit for a descendant also counts towards the justified block
+		let mut current = signed.precommit.target_hash;
+		while current != hash {
+			match ancestry.get(&current) {
+				Some(ancestor) if ancestor.number() > header.number() =>
+					current = *ancestor.parent_hash(),
+				_ => return Err(Error::InvalidAncestry),
+			}
+		}
+
+		let message = finality_grandpa::Message::Precommit(signed.precommit.clone());
+		if !sp_finality_grandpa::check_message_signature(
+			&message,
+			&signed.id,
+			&signed.signature,
+			justification.round,
+			authorities.set_id,
+		) {
+			return Err(Error::BadSignature)
+		}
+
+		weight = weight.saturating_add(voter_weight);
+	}
+
+	if weight < threshold {
+		return Err(Error::NotEnoughWeight)
+	}
+
+	Ok(())
+}
+
+/// Read `key` from a storage proof taken against `state_root`.
+pub fn read_storage_proof<Header: HeaderT>(
+	state_root: &Header::Hash,
+	nodes: Vec<Vec<u8>>,
+	key: &[u8],
+) -> Result<Option<Vec<u8>>, Error> {
+	let db = StorageProof::new(nodes).into_memory_db::<Header::Hashing>();
+	sp_trie::read_trie_value::<LayoutV1<Header::Hashing>, _>(&db, state_root, key, None, None)
+		.map_err(|_| Error::InvalidStorageProof)
+}
@@ -0,0 +1,275 @@
+use super::*;
+use sp_core::H256;
+use sp_keyring::Ed25519Keyring;
+use sp_runtime::{generic, traits::BlakeTwo256};
+use sp_trie::{recorder::Recorder, trie_types::TrieDBMutBuilderV1, MemoryDB, TrieMut};
+
+type Header = generic::Header<u32, BlakeTwo256>;
+
+fn header(number: u32, parent_hash: H256) -> Header {
+	Header::new(number, Default::default(), Default::default(), parent_hash, Default::default())
+}
+
+/// A state trie holding `Proofs` entries for `claims`, and its root.
+fn state(claims: &[(&[u8], (u64, u32))]) -> (MemoryDB<BlakeTwo256>, H256) {
+	let mut db = MemoryDB::default();
+	let mut root = H256::default();
+	{
+		let mut trie = TrieDBMutBuilderV1::<BlakeTwo256>::new(&mut db, &mut root).build();
+		trie.insert(b":code", b"runtime").unwrap();
+		for (claim, value) in claims {
+			trie.insert(&claim_storage_key(b"PoeModule", claim), &value.encode()).unwrap();
+		}
+	}
+	(db, root)
+}
+
+/// The trie nodes read when looking `claim` up, as in `state_getReadProof`.
+fn read_proof(db: &MemoryDB<BlakeTwo256>, root: &H256, claim: &[u8]) -> Vec<Vec<u8>> {
+	let recorder = Recorder::<BlakeTwo256>::default();
+	let key = claim_storage_key(b"PoeModule", claim);
+	sp_trie::read_trie_value::<LayoutV1<BlakeTwo256>, _>(
+		db,
+		root,
+		&key,
+		Some(&mut recorder.as_trie_recorder()),
+		None,
+	)
+	.unwrap();
+	recorder.drain_storage_proof().into_iter_nodes().collect()
+}
+
+/// A proof of `claim` at a block with `root` as state root, finalized by Alice and Bob.
+fn claim_proof(root: H256, claim: &[u8], nodes: Vec<Vec<u8>>) -> ClaimProof<Header> {
+	let header = Header::new(1, Default::default(), root, Default::default(), Default::default());
+	let justification = justification(
+		&header,
+		&[(Ed25519Keyring::Alice, &header), (Ed25519Keyring::Bob, &header)],
+		vec![],
+		0,
+	);
+	ClaimProof { claim: claim.to_vec(), header, justification: Some(justification), proof: nodes }
+}
+
+fn authority_set(keys: &[Ed25519Keyring]) -> AuthoritySet {
+	AuthoritySet::genesis(keys.iter().map(|k| (k.public().into(), 1)).collect())
+}
+
+fn justification(
+	target: &Header,
+	voters: &[(Ed25519Keyring, &Header)],
+	votes_ancestries: Vec<Header>,
+	set_id: SetId,
+) -> Vec<u8> {
+	let round = 1;
+	let precommits = voters
+		.iter()
+		.map(|(key, voted)| {
+			let precommit = finality_grandpa::Precommit {
+				target_hash: voted.hash(),
+				target_number: *voted.number(),
+			};
+			let message = finality_grandpa::Message::Precommit(precommit.clone());
+			let payload = sp_finality_grandpa::localized_payload(round, set_id, &message);
+			finality_grandpa::SignedPrecommit {
+				precommit,
+				signature: key.sign(&payload).into(),
+				id: key.public().into(),
+			}
+		})
+		.collect();
+
+	GrandpaJustification::<Header> {
+		round,
+		commit: finality_grandpa::Commit {
+			target_hash: target.hash(),
+			target_number: *target.number(),
+			precommits,
+		},
+		votes_ancestries,
+	}
+	.encode()
+}
+
+#[test]
+fn claim_storage_key_matches_blake2_128_concat_layout() {
+	let claim = vec![0u8, 1];
+	let key = claim_storage_key(b"PoeModule", &claim);
+	let encoded_claim = claim.encode();
+
+	assert_eq!(&key[..16], &twox_128(b"PoeModule"));
+	assert_eq!(&key[16..32], &twox_128(b"Proofs"));
+	assert_eq!(&key[32..48], &blake2_128(&encoded_claim));
+	assert_eq!(&key[48..], &encoded_claim[..]);
+	assert_eq!(&key[..32], &proofs_storage_prefix(b"PoeModule")[..]);
+	assert_eq!(claim_from_storage_key(&key), Some(claim));
+}
+
+#[test]
+fn verify_justification_works() {
+	let keys = [Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+	let target = header(1, Default::default());
+	let child = header(2, target.hash());
+
+	// two of three votes for the target itself
+	let encoded = justification(
+		&target,
+		&[(Ed25519Keyring::Alice, &target), (Ed25519Keyring::Bob, &target)],
+		vec![],
+		0,
+	);
+	assert_eq!(verify_justification(&target, &encoded, &authority_set(&keys)), Ok(()));
+
+	// a vote for a descendant counts once its ancestry is included
+	let encoded = justification(
+		&target,
+		&[(Ed25519Keyring::Alice, &target), (Ed25519Keyring::Bob, &child)],
+		vec![child.clone()],
+		0,
+	);
+	assert_eq!(verify_justification(&target, &encoded, &authority_set(&keys)), Ok(()));
+}
+
+#[test]
+fn verify_justification_fails() {
+	let keys = [Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+	let set = authority_set(&keys);
+	let target = header(1, Default::default());
+	let child = header(2, target.hash());
+
+	// not enough votes
+	let encoded = justification(&target, &[(Ed25519Keyring::Alice, &target)], vec![], 0);
+	assert_eq!(verify_justification(&target, &encoded, &set), Err(Error::NotEnoughWeight));
+
+	// signed for another set id
+	let encoded = justification(
+		&target,
+		&[(Ed25519Keyring::Alice, &target), (Ed25519Keyring::Bob, &target)],
+		vec![],
+		1,
+	);
+	assert_eq!(verify_justification(&target, &encoded, &set), Err(Error::BadSignature));
+
+	// voter outside of the trusted set
+	let encoded = justification(
+		&target,
+		&[(Ed25519Keyring::Alice, &target), (Ed25519Keyring::Dave, &target)],
+		vec![],
+		0,
+	);
+	assert_eq!(verify_justification(&target, &encoded, &set), Err(Error::UnknownAuthority));
+
+	// vote for a descendant without ancestry
+	let encoded = justification(
+		&target,
+		&[(Ed25519Keyring::Alice, &target), (Ed25519Keyring::Bob, &child)],
+		vec![],
+		0,
+	);
+	assert_eq!(verify_justification(&target, &encoded, &set), Err(Error::InvalidAncestry));
+
+	// justification of another block
+	let encoded = justification(
+		&child,
+		&[(Ed25519Keyring::Alice, &child), (Ed25519Keyring::Bob, &child)],
+		vec![],
+		0,
+	);
+	assert_eq!(
+		verify_justification(&target, &encoded, &set),
+		Err(Error::JustificationTargetMismatch)
+	);
+}
+
+#[test]
+fn verify_requires_justification() {
+	let proof = ClaimProof {
+		claim: vec![0, 1],
+		header: header(1, Default::default()),
+		justification: None,
+		proof: vec![],
+	};
+
+	assert_eq!(
+		verify::<_, (u64, u32)>(b"PoeModule", &proof, &authority_set(&[Ed25519Keyring::Alice])),
+		Err(Error::MissingJustification)
+	);
+}
+
+#[test]
+fn verify_works() {
+	let keys = [Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+	let (db, root) = state(&[(b"claim", (7, 42)), (b"other", (8, 43))]);
+
+	let proof = claim_proof(root, b"claim", read_proof(&db, &root, b"claim"));
+	assert_eq!(
+		verify::<_, (u64, u32)>(b"PoeModule", &proof, &authority_set(&keys)),
+		Ok(Some((7, 42)))
+	);
+
+	// the claim is provably absent
+	let proof = claim_proof(root, b"missing", read_proof(&db, &root, b"missing"));
+	assert_eq!(verify::<_, (u64, u32)>(b"PoeModule", &proof, &authority_set(&keys)), Ok(None));
+}
+
+#[test]
+fn verify_rejects_tampered_proof() {
+	let keys = [Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+	let (db, root) = state(&[(b"claim", (7, 42))]);
+	let nodes = read_proof(&db, &root, b"claim");
+
+	// a node that no longer hashes to what its parent references
+	for index in 0..nodes.len() {
+		let mut tampered = nodes.clone();
+		let last = tampered[index].len() - 1;
+		tampered[index][last] ^= 1;
+		let proof = claim_proof(root, b"claim", tampered);
+		assert_eq!(
+			verify::<_, (u64, u32)>(b"PoeModule", &proof, &authority_set(&keys)),
+			Err(Error::InvalidStorageProof)
+		);
+	}
+
+	// the proof of another claim can't be passed off for this one
+	let (db, root) = state(&[(b"claim", (7, 42)), (b"other", (8, 43))]);
+	let proof = claim_proof(root, b"claim", read_proof(&db, &root, b"other"));
+	assert_eq!(
+		verify::<_, (u64, u32)>(b"PoeModule", &proof, &authority_set(&keys)),
+		Err(Error::InvalidStorageProof)
+	);
+}
+
+#[test]
+fn verify_rejects_wrong_authority_set() {
+	let (db, root) = state(&[(b"claim", (7, 42))]);
+	let proof = claim_proof(root, b"claim", read_proof(&db, &root, b"claim"));
+
+	let others = [Ed25519Keyring::Charlie, Ed25519Keyring::Dave, Ed25519Keyring::Eve];
+	assert_eq!(
+		verify::<_, (u64, u32)>(b"PoeModule", &proof, &authority_set(&others)),
+		Err(Error::UnknownAuthority)
+	);
+
+	// the right keys, but a later set id
+	let keys = [Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+	let mut set = authority_set(&keys);
+	set.set_id = 1;
+	assert_eq!(verify::<_, (u64, u32)>(b"PoeModule", &proof, &set), Err(Error::BadSignature));
+}
+
+#[test]
+fn verify_rejects_empty_authority_set() {
+	let target = header(1, Default::default());
+	// no precommits at all
+	let encoded = justification(&target, &[], vec![], 0);
+	assert_eq!(
+		verify_justification(&target, &encoded, &AuthoritySet::genesis(vec![])),
+		Err(Error::EmptyAuthoritySet)
+	);
+
+	let zero_weight = AuthoritySet::genesis(vec![(Ed25519Keyring::Alice.public().into(), 0)]);
+	assert_eq!(
+		verify_justification(&target, &encoded, &zero_weight),
+		Err(Error::EmptyAuthoritySet)
+	);
+}
@@ -45,7 +45,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Percent, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -111,10 +111,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 300,
+	spec_version: 400,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -264,6 +264,9 @@ parameter_types! {
 	pub FeeMultiplier: Multiplier = Multiplier::one();
 	pub KittyPalletId: PalletId = PalletId(*b"py/kitty");
 	pub KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub KittyMutationRate: Permill = Permill::from_parts(5_000);
+	pub KittyReleaseRefund: Percent = Percent::from_percent(50);
+	pub OracleMaxPriceDeviation: Permill = Permill::from_percent(5);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
@@ -287,17 +290,37 @@ impl pallet_template::Config for Runtime {
 
 impl pallet_poe::Config for Runtime {
 	type MaxClaimLength = ConstU32<512>;
+	type MaxVersions = ConstU32<1024>;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_kitties::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
-	type KittyPrice = KittyPrice;
+	type DefaultKittyPrice = KittyPrice;
 	type PalletId = KittyPalletId;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxAuctionsPerBlock = ConstU32<64>;
+	type MaxAuctionDuration = ConstU32<{ 7 * DAYS }>;
+	type MaxOffersPerKitty = ConstU32<32>;
+	type MaxKittiesOwned = ConstU32<1024>;
+	type BreedingCooldown = ConstU32<HOURS>;
+	type RevealDelay = ConstU32<2>;
+	type MaxBirthsPerBlock = ConstU32<64>;
+	type ReleaseRefund = KittyReleaseRefund;
+	type MutationRate = KittyMutationRate;
 	type AuthorityId = pallet_kitties::crypto::TestAuthId;
+	type UnixTime = Timestamp;
+	type MaxOracleAuthorities = ConstU32<16>;
+	// a price takes the median of three reports, which a single authority can't move out of the
+	// range of the others
+	type OracleQuorum = ConstU32<3>;
+	type RoundDuration = ConstU32<5>;
+	type PriceStaleAfter = ConstU32<{ 10 * MINUTES }>;
+	type MaxPriceDeviation = OracleMaxPriceDeviation;
+	type MaxWatchedSymbols = ConstU32<32>;
+	type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the offchain runtime part
@@ -423,6 +446,7 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
 		[pallet_poe, PoeModule]
+		[pallet_kitties, PalletKitties]
 	);
 }
 
@@ -536,6 +560,60 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_poe_runtime_api::PoeApi<Block> for Runtime {
+		fn lineage(claim: Vec<u8>) -> Vec<Vec<u8>> {
+			PoeModule::lineage(claim)
+		}
+
+		fn latest_version(claim: Vec<u8>) -> Option<Vec<u8>> {
+			PoeModule::latest_version(claim)
+		}
+	}
+
+	impl pallet_kitties_runtime_api::KittiesApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn traits(kitty_id: pallet_kitties::KittyId) -> Option<Vec<pallet_kitties::genetics::Trait>> {
+			PalletKitties::traits(kitty_id)
+		}
+
+		fn approved(kitty_id: pallet_kitties::KittyId) -> Option<AccountId> {
+			PalletKitties::approved(kitty_id)
+		}
+
+		fn is_approved_for_all(owner: AccountId, operator: AccountId) -> bool {
+			PalletKitties::is_approved_for_all(&owner, &operator)
+		}
+
+		fn is_approved(who: AccountId, kitty_id: pallet_kitties::KittyId) -> bool {
+			PalletKitties::is_approved(&who, kitty_id)
+		}
+
+		fn kitty(
+			kitty_id: pallet_kitties::KittyId,
+		) -> Option<pallet_kitties::KittyInfo<AccountId, Balance, BlockNumber>> {
+			PalletKitties::kitty_info(kitty_id)
+		}
+
+		fn kitties_of(
+			owner: AccountId,
+			start: u32,
+			limit: u32,
+		) -> Vec<pallet_kitties::KittyInfo<AccountId, Balance, BlockNumber>> {
+			PalletKitties::kitties_of(&owner, start, limit)
+		}
+
+		fn ancestors(kitty_id: pallet_kitties::KittyId, depth: u32) -> Vec<pallet_kitties::KittyId> {
+			PalletKitties::ancestors(kitty_id, depth)
+		}
+
+		fn descendants(kitty_id: pallet_kitties::KittyId) -> Vec<pallet_kitties::KittyId> {
+			PalletKitties::descendants(kitty_id)
+		}
+
+		fn kitties_for_sale() -> Vec<pallet_kitties::KittyInfo<AccountId, Balance, BlockNumber>> {
+			PalletKitties::kitties_for_sale()
+		}
+	}
+
 	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
 		fn account_nonce(account: AccountId) -> Index {
 			System::account_nonce(account)
@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+# Regenerates the weights of the given pallets, e.g. `./scripts/benchmark.sh pallet_poe`
+set -e
+
+PALLETS="${*:-pallet_poe pallet_kitties}"
+
+echo "*** Building the node with runtime benchmarks"
+cargo build --release --features runtime-benchmarks
+
+for pallet in $PALLETS ; do
+   echo "*** Benchmarking $pallet"
+   ./target/release/node-template benchmark pallet \
+      --chain dev \
+      --execution wasm \
+      --wasm-execution compiled \
+      --pallet "$pallet" \
+      --extrinsic '*' \
+      --steps 50 \
+      --repeat 20 \
+      --output "./pallets/${pallet#pallet_}/src/weights.rs" \
+      --template .maintain/frame-weight-template.hbs
+done